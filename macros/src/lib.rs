@@ -0,0 +1,63 @@
+//!
+//! selenium 的过程宏，通过 selenium 的 `derive`、`macros` features 开启，不要直接依赖
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod page;
+mod selector;
+
+/// 生成 Page Object 的构造函数和元素访问方法
+///
+/// 字段属性
+///
+/// - `#[root]` 查找元素的上下文，一般是 `&Driver` 或者 `Element`
+/// - `#[find(css = "...")]` 生成返回 `SResult<Element>` 的方法，字段类型为 `Find` 或 `Component<T>`
+/// - `#[find_all(xpath = "...")]` 生成返回 `SResult<Vec<Element>>` 的方法，字段类型为 `FindAll` 或 `Component<T>`
+/// - `#[wait(timeout_ms = 5000)]` 查找前等待元素出现
+///
+/// 支持的定位方式: `css`, `xpath`, `id`, `class`, `tag_name`, `link_text`, `partial_link_text`, `name`
+#[proc_macro_derive(PageObject, attributes(root, find, find_all, wait))]
+pub fn derive_page_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    page::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+///
+/// 编译时检查语法的 css 选择器
+///
+/// 没有参数时得到 `By::Css`，有参数时得到 `ByBuf::Css`。
+/// `{}` 在属性值的位置按 css 字符串转义并加上引号，在 `:nth-child()` 等的参数中只接受整数，
+/// 其他位置按 css 标识符转义
+///
+/// ```ignore
+/// let by = css!("form > input[type=submit]");
+/// let by = css!("#{} [name={}]", id, name);
+/// ```
+#[proc_macro]
+pub fn css(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as selector::Input);
+    selector::expand_css(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+///
+/// 编译时检查语法的 xpath
+///
+/// 没有参数时得到 `By::XPath`，有参数时得到 `ByBuf::XPath`，`{}` 会被替换为 xpath 字符串，
+/// 单独作为谓语的 `[{}]` 是位置，只接受整数。
+/// 只检查括号、字符串是否闭合等，不是完整的 xpath 解析
+///
+/// ```ignore
+/// let by = xpath!("//button[normalize-space()={}]", text);
+/// let by = xpath!("//li[{}]", 2);
+/// ```
+#[proc_macro]
+pub fn xpath(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as selector::Input);
+    selector::expand_xpath(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
@@ -0,0 +1,251 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    spanned::Spanned, Data, DeriveInput, Error, Field, Fields, GenericArgument, Ident, LitInt,
+    LitStr, PathArguments, Result, Type,
+};
+
+/// 字段上声明的定位方式
+struct Locate {
+    all: bool,
+    strategy: Ident,
+    value: LitStr,
+    timeout: Option<LitInt>,
+}
+
+impl Locate {
+    fn by(&self) -> Result<TokenStream> {
+        let value = &self.value;
+        let variant = match self.strategy.to_string().as_str() {
+            "css" => quote!(Css),
+            "xpath" => quote!(XPath),
+            "id" => quote!(Id),
+            "class" => quote!(Class),
+            "tag_name" => quote!(TagName),
+            "link_text" => quote!(LinkText),
+            "partial_link_text" => quote!(ParitialLinkText),
+            "name" => quote!(Name),
+            other => {
+                return Err(Error::new(
+                    self.strategy.span(),
+                    format!("unsupported locator strategy `{other}`"),
+                ))
+            }
+        };
+        Ok(quote!(::selenium::By::#variant(#value)))
+    }
+}
+
+enum Kind {
+    Root,
+    Locate(Locate),
+    Other,
+}
+
+fn parse_field(field: &Field) -> Result<Kind> {
+    let mut root = false;
+    let mut locate: Option<Locate> = None;
+    let mut timeout = None;
+
+    for attr in &field.attrs {
+        if attr.path().is_ident("root") {
+            root = true;
+        } else if attr.path().is_ident("find") || attr.path().is_ident("find_all") {
+            if locate.is_some() {
+                return Err(Error::new(attr.span(), "duplicate `find` attribute"));
+            }
+            let all = attr.path().is_ident("find_all");
+            let mut found = None;
+            attr.parse_nested_meta(|meta| {
+                let ident = meta
+                    .path
+                    .get_ident()
+                    .cloned()
+                    .ok_or_else(|| meta.error("expected a locator strategy"))?;
+                if found.is_some() {
+                    return Err(meta.error("only one locator strategy is allowed"));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                if value.value().is_empty() {
+                    return Err(Error::new(value.span(), "locator can not be empty"));
+                }
+                found = Some((ident, value));
+                Ok(())
+            })?;
+            let (strategy, value) =
+                found.ok_or_else(|| Error::new(attr.span(), "missing locator strategy"))?;
+            locate = Some(Locate {
+                all,
+                strategy,
+                value,
+                timeout: None,
+            });
+        } else if attr.path().is_ident("wait") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("timeout_ms") {
+                    timeout = Some(meta.value()?.parse::<LitInt>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `timeout_ms`"))
+                }
+            })?;
+            if timeout.is_none() {
+                return Err(Error::new(attr.span(), "missing `timeout_ms`"));
+            }
+        }
+    }
+
+    match (root, locate) {
+        (true, Some(_)) => Err(Error::new(
+            field.span(),
+            "`root` can not be used together with `find`",
+        )),
+        (true, None) => {
+            if timeout.is_some() {
+                return Err(Error::new(field.span(), "`wait` requires `find`"));
+            }
+            Ok(Kind::Root)
+        }
+        (false, Some(mut locate)) => {
+            locate.timeout = timeout;
+            Ok(Kind::Locate(locate))
+        }
+        (false, None) => {
+            if timeout.is_some() {
+                return Err(Error::new(field.span(), "`wait` requires `find`"));
+            }
+            Ok(Kind::Other)
+        }
+    }
+}
+
+/// 字段类型是 `Component<T>` 时返回 `T`
+fn component_of(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    if last.ident != "Component" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(t) => Some(t),
+        _ => None,
+    }
+}
+
+pub(crate) fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "PageObject can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            input.span(),
+            "PageObject requires a struct with named fields",
+        ));
+    };
+
+    let mut root: Option<&Field> = None;
+    let mut inits = Vec::new();
+    let mut locates = Vec::new();
+
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        match parse_field(field)? {
+            Kind::Root => {
+                if root.is_some() {
+                    return Err(Error::new(
+                        field.span(),
+                        "only one `#[root]` field is allowed",
+                    ));
+                }
+                root = Some(field);
+            }
+            Kind::Locate(locate) => {
+                inits.push(quote!(#ident: ::core::default::Default::default()));
+                locates.push((ident, &field.ty, locate));
+            }
+            Kind::Other => {
+                inits.push(quote!(#ident: ::core::default::Default::default()));
+            }
+        }
+    }
+
+    let root = root.ok_or_else(|| {
+        Error::new(
+            input.span(),
+            "PageObject requires a field marked with `#[root]`",
+        )
+    })?;
+    let root_ident = root.ident.as_ref().unwrap();
+    let root_ty = &root.ty;
+    let methods = locates
+        .iter()
+        .map(|(ident, ty, locate)| accessor(root_ident, ident, ty, locate))
+        .collect::<Result<Vec<_>>>()?;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let vis = &input.vis;
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #vis fn new(#root_ident: #root_ty) -> Self {
+                Self {
+                    #root_ident,
+                    #(#inits,)*
+                }
+            }
+
+            #(#methods)*
+        }
+
+        impl #impl_generics ::selenium::page::PageObject for #name #ty_generics #where_clause {
+            type Root = #root_ty;
+
+            fn from_root(root: Self::Root) -> Self {
+                Self::new(root)
+            }
+        }
+    })
+}
+
+fn accessor(root: &Ident, ident: &Ident, ty: &Type, locate: &Locate) -> Result<TokenStream> {
+    let by = locate.by()?;
+    let result = quote!(::core::result::Result);
+    let element = quote!(::selenium::element::Element);
+    let timeout = match &locate.timeout {
+        Some(timeout) => quote!(::core::option::Option::Some(#timeout)),
+        None => quote!(::core::option::Option::None),
+    };
+
+    // 由标记字段查找，`Find`、`FindAll` 和 `Component<T>` 决定返回值
+    Ok(match (locate.all, component_of(ty)) {
+        (false, None) => quote! {
+            pub fn #ident(&self) -> #result<#element, ::selenium::SError> {
+                self.#ident.find(&self.#root, #by, #timeout)
+            }
+        },
+        (true, None) => quote! {
+            pub fn #ident(&self) -> #result<::std::vec::Vec<#element>, ::selenium::SError> {
+                self.#ident.find(&self.#root, #by, #timeout)
+            }
+        },
+        (false, Some(component)) => quote! {
+            pub fn #ident(&self) -> #result<#component, ::selenium::SError> {
+                self.#ident.find(&self.#root, #by, #timeout)
+            }
+        },
+        (true, Some(component)) => quote! {
+            pub fn #ident(&self) -> #result<::std::vec::Vec<#component>, ::selenium::SError> {
+                self.#ident.find_all(&self.#root, #by, #timeout)
+            }
+        },
+    })
+}
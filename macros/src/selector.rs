@@ -0,0 +1,638 @@
+//!
+//! `css!` 和 `xpath!` 的实现
+//!
+//! 编译时检查选择器的语法，`{}` 占位符在运行时按所在位置转义。
+//! css 按照选择器的语法解析，xpath 只检查括号、字符串和明显错误的路径，不是完整的解析
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Error, Expr, LitStr, Result, Token,
+};
+
+pub(crate) struct Input {
+    selector: LitStr,
+    args: Vec<Expr>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let selector: LitStr = input.parse()?;
+        let mut args = Vec::new();
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            args = Punctuated::<Expr, Token![,]>::parse_terminated(input)?
+                .into_iter()
+                .collect();
+        }
+        Ok(Input { selector, args })
+    }
+}
+
+/// 拆分后的字符串和占位符，拼接时占位符位于相邻的两段之间
+type Split = std::result::Result<(Vec<String>, Vec<Hole>), String>;
+
+/// 占位符需要的转义方式
+#[derive(Debug, PartialEq)]
+enum Hole {
+    /// css 标识符，比如 id、class
+    Ident,
+    /// css 属性值
+    CssString,
+    /// xpath 字符串
+    XPathString,
+    /// 只接受整数，比如 nth-child 的参数、xpath 中的 `[{}]`
+    Number,
+}
+
+impl Hole {
+    /// 用于检查语法的替代值
+    fn sample(&self) -> &'static str {
+        match self {
+            Hole::Ident => "x",
+            Hole::CssString => "\"x\"",
+            Hole::XPathString => "'x'",
+            Hole::Number => "1",
+        }
+    }
+}
+
+pub(crate) fn expand_css(input: Input) -> Result<TokenStream> {
+    expand(input, "Css", "css", css_holes, validate_css)
+}
+
+pub(crate) fn expand_xpath(input: Input) -> Result<TokenStream> {
+    expand(input, "XPath", "xpath", xpath_holes, validate_xpath)
+}
+
+fn expand(
+    input: Input,
+    variant: &str,
+    module: &str,
+    holes: fn(&str) -> Split,
+    validate: fn(&str) -> std::result::Result<(), String>,
+) -> Result<TokenStream> {
+    let span = input.selector.span();
+    let value = input.selector.value();
+    let (pieces, holes) = holes(&value).map_err(|e| Error::new(span, e))?;
+    if holes.len() != input.args.len() {
+        return Err(Error::new(
+            span,
+            format!(
+                "{} placeholders but {} arguments were given",
+                holes.len(),
+                input.args.len()
+            ),
+        ));
+    }
+
+    let mut sample = String::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        sample.push_str(piece);
+        if let Some(hole) = holes.get(i) {
+            sample.push_str(hole.sample());
+        }
+    }
+    validate(&sample).map_err(|e| {
+        Error::new(
+            span,
+            format!("invalid {} `{value}`: {e}", variant.to_lowercase()),
+        )
+    })?;
+
+    let variant = syn::Ident::new(variant, span);
+    let module = syn::Ident::new(module, span);
+    if holes.is_empty() {
+        return Ok(quote!(::selenium::By::#variant(#value)));
+    }
+    // 拼回去时 `{` 和 `}` 需要转义
+    let format = pieces
+        .iter()
+        .map(|f| f.replace('{', "{{").replace('}', "}}"))
+        .collect::<Vec<_>>()
+        .join("{}");
+    let args = holes.iter().zip(&input.args).map(|(hole, arg)| {
+        let escape = match hole {
+            Hole::Ident => quote!(::selenium::css::escape),
+            Hole::CssString => quote!(::selenium::css::quote),
+            Hole::XPathString => quote!(::selenium::xpath::quote),
+            // 整数不需要转义，类型不对时编译报错
+            Hole::Number => return quote!(::selenium::#module::number(#arg)),
+        };
+        quote!(#escape(&::std::string::ToString::to_string(&(#arg))))
+    });
+    Ok(quote!(::selenium::ByBuf::#variant(::std::format!(#format, #(#args),*))))
+}
+
+/// 按 `{}` 拆分，并判断每个占位符在 css 中的位置
+fn css_holes(s: &str) -> Split {
+    let mut pieces = vec![String::new()];
+    let mut holes = Vec::new();
+    let mut in_bracket = false;
+    let mut after_operator = false;
+    let mut quote: Option<char> = None;
+    // 每层括号的参数类型，:not(...) 等的参数仍是选择器
+    let mut parens: Vec<Paren> = Vec::new();
+    let mut seen = String::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '{' && chars.peek() == Some(&'}') {
+            chars.next();
+            if quote.is_some() {
+                return Err(
+                    "placeholders are quoted automatically, remove the quotes around `{}`"
+                        .to_string(),
+                );
+            }
+            holes.push(if in_bracket && after_operator {
+                Hole::CssString
+            } else if parens.last() == Some(&Paren::Nth) {
+                Hole::Number
+            } else {
+                Hole::Ident
+            });
+            seen.push('x');
+            pieces.push(String::new());
+            continue;
+        }
+        match (quote, c) {
+            (Some(_), '\\') => {
+                pieces.last_mut().unwrap().push(c);
+                if let Some(n) = chars.next() {
+                    pieces.last_mut().unwrap().push(n);
+                }
+                continue;
+            }
+            (Some(q), c) if q == c => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[') => {
+                in_bracket = true;
+                after_operator = false;
+            }
+            (None, ']') => in_bracket = false,
+            (None, '=') => after_operator = true,
+            (None, '(') => {
+                let name = seen
+                    .trim_end_matches(|c: char| c.is_alphanumeric() || c == '-' || c == '_')
+                    .len();
+                parens.push(match seen[name..].to_ascii_lowercase().as_str() {
+                    "not" | "is" | "where" | "matches" | "has" => Paren::Selector,
+                    "nth-child" | "nth-last-child" | "nth-of-type" | "nth-last-of-type"
+                    | "nth-col" | "nth-last-col" => Paren::Nth,
+                    _ => Paren::Other,
+                });
+            }
+            (None, ')') => {
+                parens.pop();
+            }
+            // `:nth-child(2n of .a)` 中 of 之后是选择器
+            (None, ' ') if parens.last() == Some(&Paren::Nth) && seen.ends_with(" of") => {
+                *parens.last_mut().unwrap() = Paren::Selector;
+            }
+            (None, '{' | '}') => return Err("unexpected `{` or `}`".to_string()),
+            _ => {}
+        }
+        seen.push(c);
+        pieces.last_mut().unwrap().push(c);
+    }
+    Ok((pieces, holes))
+}
+
+/// 伪类参数的类型
+#[derive(Debug, PartialEq)]
+enum Paren {
+    /// `:not(...)` 等，参数是选择器
+    Selector,
+    /// `:nth-child(...)` 等，参数是 `an+b`
+    Nth,
+    /// `:lang(...)` 等，参数作为标识符
+    Other,
+}
+
+/// 按 `{}` 拆分，单独作为谓语的占位符 `[{}]` 是位置，只接受整数，其他都作为字符串
+fn xpath_holes(s: &str) -> Split {
+    let mut pieces = vec![String::new()];
+    let mut holes = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '{' && chars.peek() == Some(&'}') {
+            chars.next();
+            if quote.is_some() {
+                return Err(
+                    "placeholders are quoted automatically, remove the quotes around `{}`"
+                        .to_string(),
+                );
+            }
+            let open = pieces.last().unwrap().trim_end().ends_with('[');
+            let close = chars.clone().find(|c| !c.is_whitespace()) == Some(']');
+            holes.push(if open && close {
+                Hole::Number
+            } else {
+                Hole::XPathString
+            });
+            pieces.push(String::new());
+            continue;
+        }
+        match (quote, c) {
+            (Some(q), c) if q == c => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{' | '}') => return Err("unexpected `{` or `}`".to_string()),
+            _ => {}
+        }
+        pieces.last_mut().unwrap().push(c);
+    }
+    Ok((pieces, holes))
+}
+
+fn validate_css(s: &str) -> std::result::Result<(), String> {
+    let mut p = CssParser {
+        chars: s.chars().collect(),
+        pos: 0,
+    };
+    p.selector_list(false)?;
+    match p.peek() {
+        None => Ok(()),
+        Some(c) => Err(p.unexpected(c)),
+    }
+}
+
+/// 只检查语法，不关心伪类名等是否存在
+struct CssParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl CssParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn unexpected(&self, c: char) -> String {
+        format!("unexpected `{c}` at {}", self.pos)
+    }
+
+    fn expected(&self, what: &str) -> String {
+        match self.peek() {
+            Some(c) => format!("expected {what} at {}, found `{c}`", self.pos),
+            None => format!("expected {what} at {}, found end of selector", self.pos),
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn selector_list(&mut self, relative: bool) -> std::result::Result<(), String> {
+        loop {
+            self.skip_whitespace();
+            self.complex(relative)?;
+            self.skip_whitespace();
+            if self.peek() != Some(',') {
+                return Ok(());
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn complex(&mut self, relative: bool) -> std::result::Result<(), String> {
+        if relative && matches!(self.peek(), Some('>' | '+' | '~')) {
+            self.pos += 1;
+            self.skip_whitespace();
+        }
+        self.compound()?;
+        loop {
+            let whitespace = self.skip_whitespace();
+            match self.peek() {
+                Some('>' | '+' | '~') => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    self.compound()?;
+                }
+                Some(',' | ')') | None => return Ok(()),
+                Some(_) if whitespace => self.compound()?,
+                Some(c) => return Err(self.unexpected(c)),
+            }
+        }
+    }
+
+    fn compound(&mut self) -> std::result::Result<(), String> {
+        let start = self.pos;
+        match self.peek() {
+            Some('*') => self.pos += 1,
+            Some(c) if is_ident_start(c) || c == '-' => self.ident()?,
+            _ => {}
+        }
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.pos += 1;
+                    if !self.name() {
+                        return Err(self.expected("id"));
+                    }
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    self.ident()?;
+                }
+                Some('[') => self.attribute()?,
+                Some(':') => self.pseudo()?,
+                _ => break,
+            }
+        }
+        if self.pos == start {
+            return Err(self.expected("selector"));
+        }
+        Ok(())
+    }
+
+    fn ident(&mut self) -> std::result::Result<(), String> {
+        if self.peek() == Some('-') {
+            self.pos += 1;
+            if self.peek() == Some('-') {
+                self.pos += 1;
+                self.name();
+                return Ok(());
+            }
+        }
+        match self.peek() {
+            Some('\\') => self.escape()?,
+            Some(c) if is_ident_start(c) => self.pos += 1,
+            _ => return Err(self.expected("identifier")),
+        }
+        self.name();
+        Ok(())
+    }
+
+    /// 标识符剩余的部分，返回是否有内容
+    fn name(&mut self) -> bool {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    if self.escape().is_err() {
+                        break;
+                    }
+                }
+                Some(c) if is_ident_start(c) || c.is_ascii_digit() || c == '-' => self.pos += 1,
+                _ => break,
+            }
+        }
+        self.pos > start
+    }
+
+    fn escape(&mut self) -> std::result::Result<(), String> {
+        self.pos += 1;
+        match self.peek() {
+            None | Some('\n') => Err(self.expected("escaped character")),
+            Some(c) if c.is_ascii_hexdigit() => {
+                let mut n = 0;
+                while n < 6 && matches!(self.peek(), Some(c) if c.is_ascii_hexdigit()) {
+                    self.pos += 1;
+                    n += 1;
+                }
+                if matches!(self.peek(), Some(c) if c.is_whitespace()) {
+                    self.pos += 1;
+                }
+                Ok(())
+            }
+            Some(_) => {
+                self.pos += 1;
+                Ok(())
+            }
+        }
+    }
+
+    fn string(&mut self) -> std::result::Result<(), String> {
+        let quote = self.peek();
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                None | Some('\n') => return Err("unterminated string".to_string()),
+                Some('\\') => self.pos += 2,
+                c if c == quote => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn attribute(&mut self) -> std::result::Result<(), String> {
+        self.pos += 1;
+        self.skip_whitespace();
+        self.ident()?;
+        self.skip_whitespace();
+        match self.peek() {
+            Some(']') => {
+                self.pos += 1;
+                return Ok(());
+            }
+            Some('=') => self.pos += 1,
+            Some('~' | '|' | '^' | '$' | '*') if self.chars.get(self.pos + 1) == Some(&'=') => {
+                self.pos += 2
+            }
+            _ => return Err(self.expected("`]` or attribute operator")),
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"' | '\'') => self.string()?,
+            _ => self.ident()?,
+        }
+        self.skip_whitespace();
+        if matches!(self.peek(), Some('i' | 'I' | 's' | 'S')) {
+            self.pos += 1;
+            self.skip_whitespace();
+        }
+        if self.peek() != Some(']') {
+            return Err(self.expected("`]`"));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn pseudo(&mut self) -> std::result::Result<(), String> {
+        self.pos += 1;
+        if self.peek() == Some(':') {
+            self.pos += 1;
+        }
+        let start = self.pos;
+        self.ident()?;
+        let name: String = self.chars[start..self.pos].iter().collect();
+        if self.peek() != Some('(') {
+            return Ok(());
+        }
+        self.pos += 1;
+        match name.to_ascii_lowercase().as_str() {
+            "not" | "is" | "where" | "matches" => self.selector_list(false)?,
+            "has" => self.selector_list(true)?,
+            _ => {
+                // nth-child 等的参数只检查括号和字符串
+                let mut depth = 0;
+                loop {
+                    match self.peek() {
+                        None => break,
+                        Some('"' | '\'') => self.string()?,
+                        Some('(') => {
+                            depth += 1;
+                            self.pos += 1;
+                        }
+                        Some(')') if depth == 0 => break,
+                        Some(')') => {
+                            depth -= 1;
+                            self.pos += 1;
+                        }
+                        Some(_) => self.pos += 1,
+                    }
+                }
+            }
+        }
+        self.skip_whitespace();
+        if self.peek() != Some(')') {
+            return Err(self.expected("`)`"));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
+
+///
+/// 检查括号、字符串是否闭合以及明显错误的路径
+///
+/// 不解析 xpath 的语法，函数名、轴等错误要到浏览器执行时才会发现
+fn validate_xpath(s: &str) -> std::result::Result<(), String> {
+    if s.trim().is_empty() {
+        return Err("empty expression".to_string());
+    }
+    let chars: Vec<char> = s.chars().collect();
+    let mut stack = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' | '"' => match chars[i + 1..].iter().position(|f| *f == c) {
+                Some(n) => i += n + 1,
+                None => return Err(format!("unterminated string at {i}")),
+            },
+            '(' | '[' => {
+                let close = if c == '(' { ')' } else { ']' };
+                if c == '[' && chars[i + 1..].iter().find(|f| !f.is_whitespace()) == Some(&']') {
+                    return Err(format!("empty predicate at {i}"));
+                }
+                stack.push(close);
+            }
+            ')' | ']' => match stack.pop() {
+                Some(close) if close == c => {}
+                _ => return Err(format!("unexpected `{c}` at {i}")),
+            },
+            '/' if chars.get(i + 1) == Some(&'/') && chars.get(i + 2) == Some(&'/') => {
+                return Err(format!("unexpected `///` at {i}"));
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    if let Some(close) = stack.pop() {
+        return Err(format!("missing `{close}`"));
+    }
+    let trimmed = s.trim_end();
+    if trimmed != "/" {
+        if let Some(c) = trimmed
+            .chars()
+            .last()
+            .filter(|c| matches!(c, '/' | ',' | '|' | '=' | '<' | '>' | '!' | '+' | '@'))
+        {
+            return Err(format!("unexpected `{c}` at end of expression"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{css_holes, validate_css, validate_xpath, xpath_holes, Hole};
+
+    #[test]
+    fn css() {
+        for ok in [
+            "#id",
+            "div.a.b > span + i ~ b",
+            "a[href^='http'], a[target=_blank i]",
+            "*",
+            "input[type=\"text\"]:not(.hidden, [disabled])",
+            "li:nth-child(2n + 1)::before",
+            "div:has(> img)",
+            "#\\31 23",
+            "-foo",
+            "--var",
+            "中文",
+        ] {
+            assert_eq!(Ok(()), validate_css(ok), "{ok}");
+        }
+        for err in [
+            "", "div >", "a[href", "a[href=]", "#", ".1a", "a:not(", "a,", "a)", "[a='b]", "a >> b",
+        ] {
+            assert!(validate_css(err).is_err(), "{err}");
+        }
+    }
+
+    #[test]
+    fn xpath() {
+        for ok in [
+            "//div[@id='a']/span",
+            "/",
+            "(//a)[1]",
+            "//*[contains(text(), \"a]b\")]",
+            "//a | //b",
+        ] {
+            assert_eq!(Ok(()), validate_xpath(ok), "{ok}");
+        }
+        for err in [
+            "",
+            "//div[",
+            "//div[]",
+            "//a/",
+            "///a",
+            "//a[@id='a]",
+            "//a)",
+        ] {
+            assert!(validate_xpath(err).is_err(), "{err}");
+        }
+    }
+
+    #[test]
+    fn holes() {
+        let (pieces, holes) = css_holes("#{} [name={}] [a='b']").unwrap();
+        assert_eq!(vec!["#", " [name=", "] [a='b']"], pieces);
+        assert_eq!(vec![Hole::Ident, Hole::CssString], holes);
+        assert!(css_holes("[name='{}']").is_err());
+
+        let (pieces, holes) = css_holes("li:nth-child({}):not(#{}, :nth-of-type({}))").unwrap();
+        assert_eq!(
+            vec!["li:nth-child(", "):not(#", ", :nth-of-type(", "))"],
+            pieces
+        );
+        assert_eq!(vec![Hole::Number, Hole::Ident, Hole::Number], holes);
+
+        let (_, holes) = css_holes("p:lang({}) li:nth-child(2n of .{})").unwrap();
+        assert_eq!(vec![Hole::Ident, Hole::Ident], holes);
+
+        let (pieces, holes) = xpath_holes("//a[text()={}]").unwrap();
+        assert_eq!(vec!["//a[text()=", "]"], pieces);
+        assert_eq!(vec![Hole::XPathString], holes);
+        assert!(xpath_holes("//a[text()='{}']").is_err());
+
+        let (_, holes) = xpath_holes("(//li)[ {} ]/a[@id={}]").unwrap();
+        assert_eq!(vec![Hole::Number, Hole::XPathString], holes);
+    }
+}
@@ -0,0 +1,105 @@
+//!
+//! 自动等待元素可操作，通过 [Driver::set_auto_wait](crate::driver::Driver::set_auto_wait) 开启
+//!
+use std::{
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{actions::Origin, driver::Session, http::Http, ErrorKind, SError, SResult};
+
+const ACTIONABLE_SCRIPT: &str = include_str!("js/actionable.js");
+
+/// 两次检查之间的间隔
+const POLLING: Duration = Duration::from_millis(100);
+
+/// 附加在 attached、visible 之外的检查
+#[derive(Serialize, Clone, Copy)]
+pub(crate) struct Checks {
+    enabled: bool,
+    stable: bool,
+    hit: bool,
+}
+
+impl Checks {
+    pub(crate) const CLICK: Checks = Checks {
+        enabled: true,
+        stable: true,
+        hit: true,
+    };
+    pub(crate) const INPUT: Checks = Checks {
+        enabled: true,
+        stable: false,
+        hit: false,
+    };
+    pub(crate) const POINTER: Checks = Checks {
+        enabled: false,
+        stable: true,
+        hit: true,
+    };
+}
+
+/// 元素不可操作的原因，可操作时返回 `None`
+fn blocker(
+    http: &Http,
+    session: &Session,
+    target: &Origin,
+    checks: Checks,
+) -> SResult<Option<String>> {
+    http.execute_async_script_with_atom(
+        &session.session_id,
+        ACTIONABLE_SCRIPT,
+        vec![serde_json::to_value(target)?, serde_json::to_value(checks)?],
+    )
+}
+
+///
+/// 所有元素都可操作后再执行 `action`
+///
+/// 未开启自动等待时直接执行；元素不可操作或执行时报 intercepted、not interactable 会一直重试到超时，元素失效时直接返回错误
+pub(crate) fn run<T>(
+    http: &Http,
+    session: &Session,
+    targets: &[&Origin],
+    checks: Checks,
+    mut action: impl FnMut() -> SResult<T>,
+) -> SResult<T> {
+    let Some(timeout) = session.auto_wait.get() else {
+        return action();
+    };
+    let deadline = Instant::now() + timeout;
+    loop {
+        let mut last = None;
+        for target in targets {
+            match blocker(http, session, target, checks) {
+                Ok(None) => {}
+                Ok(Some(reason)) => {
+                    last = Some(SError::Timeout(format!("element not actionable: {reason}")));
+                    break;
+                }
+                // 包括 stale element reference，元素失效后不会恢复
+                Err(e) => return Err(e),
+            }
+        }
+        let last = match last {
+            Some(e) => e,
+            None => match action() {
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        ErrorKind::ElementClickIntercepted | ErrorKind::ElementNotInteractable
+                    ) =>
+                {
+                    e
+                }
+                r => return r,
+            },
+        };
+        if Instant::now() >= deadline {
+            return Err(last);
+        }
+        sleep(POLLING.min(deadline.saturating_duration_since(Instant::now())));
+    }
+}
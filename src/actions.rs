@@ -4,12 +4,14 @@ use bon::Builder;
 use serde::{ser::SerializeMap, Serialize, Serializer};
 
 use crate::{
+    actionability::{self, Checks},
     driver::Session,
     element::Element,
     http::{ActionRequest, Http},
-    SResult,
+    SError, SResult,
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Button {
     LEFT = 0,
     MIDDLE = 1,
@@ -33,6 +35,7 @@ impl Serialize for Button {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ActionType {
     KeyDown,
     KeyUp,
@@ -67,11 +70,22 @@ impl Serialize for ActionType {
     }
 }
 
+/// 指针移动时坐标的参照
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Origin {
+    /// 相对于视口左上角
     Viewport,
+    /// 相对于指针当前的位置
     Pointer,
+    /// 相对于元素在视口中可见部分的中心
     Element(String, String),
 }
+
+impl From<&Element> for Origin {
+    fn from(value: &Element) -> Self {
+        value.origin()
+    }
+}
 impl Serialize for Origin {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -94,10 +108,12 @@ pub(crate) enum Device<'a> {
     Pointer(&'a Pointer),
     Keyboard(&'a Keyboard),
     Wheel(&'a Wheel),
+    /// 补齐 tick 的空操作
+    Pause,
 }
 
 /// 鼠标、触摸等操作
-#[derive(Serialize, Builder)]
+#[derive(Clone, Debug, Serialize, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct Pointer {
     #[serde(rename(serialize = "type"))]
@@ -112,24 +128,31 @@ pub struct Pointer {
     pub(crate) x: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) y: Option<i32>,
+    /// 接触面的宽度，单位是像素
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) width: Option<u32>,
+    pub(crate) width: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) height: Option<u32>,
+    pub(crate) height: Option<f64>,
+    /// 压力，0到1
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) pressure: Option<u32>,
+    pub(crate) pressure: Option<f64>,
+    /// 切向压力，-1到1
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) tangential_pressure: Option<u32>,
+    pub(crate) tangential_pressure: Option<f64>,
+    /// 笔的倾斜角度，-90到90
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) tilt_x: Option<u32>,
+    pub(crate) tilt_x: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) tilt_y: Option<u32>,
+    pub(crate) tilt_y: Option<i32>,
+    /// 笔绕自身轴旋转的角度，0到359
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) twist: Option<i32>,
+    pub(crate) twist: Option<u32>,
+    /// 弧度，0到π/2
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) altitude_angle: Option<i32>,
+    pub(crate) altitude_angle: Option<f64>,
+    /// 弧度，0到2π
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) azimuth_angle: Option<i32>,
+    pub(crate) azimuth_angle: Option<f64>,
 }
 
 impl Default for Pointer {
@@ -155,25 +178,19 @@ impl Default for Pointer {
 }
 
 impl Pointer {
-    pub(crate) fn parameters() -> HashMap<String, String> {
-        let mut v = HashMap::new();
-        v.insert("pointerType".to_string(), "mouse".to_string());
-        v
-    }
-
     pub fn press(button: Button) -> Self {
         Pointer::builder()
             .r#type(ActionType::PointerDown)
             .button(button)
-            .width(0)
-            .height(0)
-            .pressure(0)
-            .tangential_pressure(0)
+            .width(0.0)
+            .height(0.0)
+            .pressure(0.0)
+            .tangential_pressure(0.0)
             .tilt_x(0)
             .tilt_y(0)
             .twist(0)
-            .altitude_angle(0)
-            .azimuth_angle(0)
+            .altitude_angle(0.0)
+            .azimuth_angle(0.0)
             .build()
     }
 
@@ -194,15 +211,26 @@ impl Pointer {
             .x(0)
             .y(0)
             .duration(100)
-            .width(0)
-            .height(0)
-            .pressure(0)
-            .tangential_pressure(0)
+            .width(0.0)
+            .height(0.0)
+            .pressure(0.0)
+            .tangential_pressure(0.0)
             .tilt_x(0)
             .tilt_y(0)
             .twist(0)
-            .altitude_angle(0)
-            .azimuth_angle(0)
+            .altitude_angle(0.0)
+            .azimuth_angle(0.0)
+            .build()
+    }
+
+    /// 在 `duration` 毫秒内移动到相对于 `origin` 的 `(x, y)`
+    pub fn move_to(origin: Origin, x: i32, y: i32, duration: u32) -> Self {
+        Pointer::builder()
+            .r#type(ActionType::PointerMove)
+            .origin(origin)
+            .x(x)
+            .y(y)
+            .duration(duration)
             .build()
     }
 
@@ -214,7 +242,118 @@ impl Pointer {
     }
 }
 
-#[derive(Serialize, Builder)]
+/// 指针设备的类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PointerKind {
+    Mouse,
+    Touch,
+    Pen,
+}
+
+impl PointerKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PointerKind::Mouse => "mouse",
+            PointerKind::Touch => "touch",
+            PointerKind::Pen => "pen",
+        }
+    }
+
+    pub(crate) fn parameters(&self) -> HashMap<String, String> {
+        let mut v = HashMap::new();
+        v.insert("pointerType".to_string(), self.as_str().to_string());
+        v
+    }
+}
+
+///
+/// 有名字的指针设备，比如一根手指或者一支笔
+///
+/// 用 [Action::parallel] 可以让多个设备同时执行
+///
+/// ```no_run
+/// use selenium::option::FirefoxBuilder;
+/// use selenium::driver::Driver;
+/// use selenium::actions::{Origin, PointerSource};
+/// use selenium::By;
+/// let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+/// let canvas = driver.find_element(By::Id("canvas")).unwrap();
+/// let pen = PointerSource::pen("pen")
+///     .move_to(Origin::from(&canvas), -50, 0, 0)
+///     .down()
+///     .move_to(Origin::Pointer, 100, 0, 500)
+///     .up();
+/// driver.actions().add_source(pen).perform().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct PointerSource {
+    pub(crate) id: String,
+    pub(crate) kind: PointerKind,
+    pub(crate) actions: Vec<Pointer>,
+}
+
+impl PointerSource {
+    pub fn new(id: &str, kind: PointerKind) -> Self {
+        PointerSource {
+            id: id.to_string(),
+            kind,
+            actions: Vec::new(),
+        }
+    }
+
+    pub fn mouse(id: &str) -> Self {
+        PointerSource::new(id, PointerKind::Mouse)
+    }
+
+    pub fn touch(id: &str) -> Self {
+        PointerSource::new(id, PointerKind::Touch)
+    }
+
+    pub fn pen(id: &str) -> Self {
+        PointerSource::new(id, PointerKind::Pen)
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn kind(&self) -> PointerKind {
+        self.kind
+    }
+
+    /// 添加任意操作，比如带压力和角度的笔
+    pub fn action(mut self, pointer: Pointer) -> Self {
+        self.actions.push(pointer);
+        self
+    }
+
+    pub fn move_to(self, origin: Origin, x: i32, y: i32, duration: u32) -> Self {
+        self.action(Pointer::move_to(origin, x, y, duration))
+    }
+
+    /// 按下，触摸和笔相当于接触屏幕
+    pub fn down(self) -> Self {
+        self.press(Button::LEFT)
+    }
+
+    pub fn up(self) -> Self {
+        self.release(Button::LEFT)
+    }
+
+    pub fn press(self, button: Button) -> Self {
+        self.action(Pointer::press(button))
+    }
+
+    pub fn release(self, button: Button) -> Self {
+        self.action(Pointer::release(button))
+    }
+
+    pub fn pause(self, duration: u32) -> Self {
+        self.action(Pointer::pause(duration))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct Keyboard {
     #[serde(rename(serialize = "type"))]
@@ -253,7 +392,7 @@ impl Keyboard {
 ///
 /// 鼠标滚轮
 ///
-#[derive(Serialize, Builder)]
+#[derive(Clone, Debug, Serialize, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct Wheel {
     #[serde(rename(serialize = "type"))]
@@ -417,41 +556,401 @@ impl Key {
     }
 }
 
+const DEFAULT_MOUSE: &str = "default mouse";
+const DEFAULT_KEYBOARD: &str = "default keyboard";
+const DEFAULT_WHEEL: &str = "default wheel";
+/// [Action::move_by] 的移动时间，同 [Pointer::move_pointer]
+const DEFAULT_MOVE_DURATION: u32 = 100;
+
+/// 元素在视口中可见部分的中心
+const CENTER_SCRIPT: &str = r#"var width = window.innerWidth;
+var height = window.innerHeight;
+return arguments[0].map(function (element) {
+  var r = element.getClientRects()[0] || element.getBoundingClientRect();
+  var left = Math.max(0, Math.min(r.left, width));
+  var right = Math.max(0, Math.min(r.right, width));
+  var top = Math.max(0, Math.min(r.top, height));
+  var bottom = Math.max(0, Math.min(r.bottom, height));
+  return [Math.floor((left + right) / 2), Math.floor((top + bottom) / 2)];
+});"#;
+
+/// 输入设备
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Source {
+    Pointer(String, PointerKind),
+    Keyboard,
+    Wheel,
+}
+
+impl Source {
+    fn mouse() -> Self {
+        Source::Pointer(DEFAULT_MOUSE.to_string(), PointerKind::Mouse)
+    }
+}
+
+/// 一个设备的一次操作
+#[derive(Clone, Debug)]
+pub(crate) enum Step {
+    Pointer(Pointer),
+    Keyboard(Keyboard),
+    Wheel(Wheel),
+}
+
+/// 同时开始的一组操作，每个设备最多一个
+pub(crate) type Tick = Vec<(Source, Step)>;
+
+///
+/// 拆分一次移动，返回中间的点、最后一次移动和移动后的位置
+///
+/// 起点或者终点未知时不拆分，比如第一次移动，或者目标元素的位置没有获取到
+fn split_move(
+    pointer: &Pointer,
+    position: Option<(i32, i32)>,
+    centers: &HashMap<String, (i32, i32)>,
+    steps: u32,
+) -> (Vec<Pointer>, Pointer, Option<(i32, i32)>) {
+    let (x, y) = (pointer.x.unwrap_or(0), pointer.y.unwrap_or(0));
+    let target = match &pointer.origin {
+        None | Some(Origin::Viewport) => Some((x, y)),
+        Some(Origin::Pointer) => position.map(|(px, py)| (px + x, py + y)),
+        Some(Origin::Element(_, id)) => centers.get(id).map(|(cx, cy)| (cx + x, cy + y)),
+    };
+    let (Some(from), Some(to)) = (position, target) else {
+        return (Vec::new(), pointer.clone(), target);
+    };
+    let total = pointer.duration.unwrap_or(0);
+    let duration = total / steps;
+    let n = steps as i32;
+    let between = (1..n)
+        .map(|i| {
+            Pointer::move_to(
+                Origin::Viewport,
+                from.0 + (to.0 - from.0) * i / n,
+                from.1 + (to.1 - from.1) * i / n,
+                duration,
+            )
+        })
+        .collect();
+    let mut last = pointer.clone();
+    last.duration = Some(total - duration * (steps - 1));
+    // 相对移动的参照已经变了，改为视口坐标
+    if last.origin == Some(Origin::Pointer) {
+        last.origin = Some(Origin::Viewport);
+        last.x = Some(to.0);
+        last.y = Some(to.1);
+    }
+    (between, last, target)
+}
+
+///
+/// 把每次移动拆分成 `steps` 段，中间的点使用视口坐标
+///
+/// 中间的点各占一个 tick，同一个 tick 中的其他操作和最后一次移动同时执行
+fn interpolate(ticks: &[Tick], centers: &HashMap<String, (i32, i32)>, steps: u32) -> Vec<Tick> {
+    let mut positions: HashMap<&str, (i32, i32)> = HashMap::new();
+    let mut v = Vec::with_capacity(ticks.len());
+    for tick in ticks {
+        let mut between: Vec<(&Source, Vec<Pointer>)> = Vec::new();
+        let mut last = Vec::with_capacity(tick.len());
+        for (source, step) in tick {
+            match (source, step) {
+                (Source::Pointer(id, _), Step::Pointer(pointer))
+                    if pointer._type == ActionType::PointerMove =>
+                {
+                    let (moves, end, target) =
+                        split_move(pointer, positions.get(id.as_str()).copied(), centers, steps);
+                    match target {
+                        Some(p) => positions.insert(id, p),
+                        None => positions.remove(id.as_str()),
+                    };
+                    between.push((source, moves));
+                    last.push((source.clone(), Step::Pointer(end)));
+                }
+                _ => last.push((source.clone(), step.clone())),
+            }
+        }
+        let n = between.iter().map(|(_, m)| m.len()).max().unwrap_or(0);
+        for i in 0..n {
+            v.push(
+                between
+                    .iter()
+                    .filter_map(|(s, m)| Some(((*s).clone(), Step::Pointer(m.get(i)?.clone()))))
+                    .collect(),
+            );
+        }
+        v.push(last);
+    }
+    v
+}
+
+/// 检查指针设备的名字，同一个 tick 中不能重复，同一个名字只能对应一种类型
+fn validate(ticks: &[Tick]) -> SResult<()> {
+    let mut kinds: Vec<(&str, PointerKind)> = Vec::new();
+    for tick in ticks {
+        let mut ids: Vec<&str> = Vec::new();
+        for (source, _) in tick {
+            let Source::Pointer(id, kind) = source else {
+                continue;
+            };
+            if id == DEFAULT_KEYBOARD || id == DEFAULT_WHEEL {
+                return Err(SError::Message(format!(
+                    "pointer `{id}` conflicts with the default input source"
+                )));
+            }
+            if ids.contains(&id.as_str()) {
+                return Err(SError::Message(format!(
+                    "pointer `{id}` is used twice in the same tick"
+                )));
+            }
+            ids.push(id);
+            match kinds.iter().find(|(f, _)| f == id) {
+                Some((_, other)) if other != kind => {
+                    return Err(SError::Message(format!(
+                        "pointer `{id}` is used as both {} and {}",
+                        other.as_str(),
+                        kind.as_str()
+                    )));
+                }
+                Some(_) => {}
+                None => kinds.push((id, *kind)),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 每个设备一个请求，没有操作的 tick 用 pause 补齐
+fn requests(ticks: &[Tick]) -> Vec<ActionRequest<'_>> {
+    let mut sources: Vec<&Source> = Vec::new();
+    for (source, _) in ticks.iter().flatten() {
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+    sources
+        .into_iter()
+        .map(|source| {
+            let actions = ticks
+                .iter()
+                .map(|tick| match tick.iter().find(|(s, _)| s == source) {
+                    Some((_, Step::Pointer(p))) => Device::Pointer(p),
+                    Some((_, Step::Keyboard(k))) => Device::Keyboard(k),
+                    Some((_, Step::Wheel(w))) => Device::Wheel(w),
+                    None => Device::Pause,
+                })
+                .collect();
+            let (_type, id, parameters) = match source {
+                Source::Pointer(id, kind) => ("pointer", id.as_str(), Some(kind.parameters())),
+                Source::Keyboard => ("key", DEFAULT_KEYBOARD, None),
+                Source::Wheel => ("wheel", DEFAULT_WHEEL, None),
+            };
+            ActionRequest {
+                actions,
+                parameters,
+                _type: _type.to_string(),
+                id: id.to_string(),
+            }
+        })
+        .collect()
+}
+
+///
+/// 按顺序执行的一组操作
+///
+/// 每次调用添加一个 tick，tick 中没有操作的设备会自动补上 pause，
+/// 所以 `key_down(Control).click(el).key_up(Control)` 会按照书写的顺序执行
 pub struct Action {
-    pub(crate) pointer: Vec<Pointer>,
-    pub(crate) keyboard: Vec<Keyboard>,
-    pub(crate) wheel: Vec<Wheel>,
+    /// 按顺序执行，同一个 tick 中的操作同时开始
+    pub(crate) ticks: Vec<Tick>,
+    /// 执行完后依然按下的键
+    pressed_keys: Vec<String>,
+    /// 执行完后依然按下的按钮，以及所在的设备
+    pressed_buttons: Vec<(String, Button)>,
+    /// 每次移动拆分成多少段
+    interpolation: Option<u32>,
     session: Rc<Session>,
     http: Rc<Http>,
 }
 impl Action {
     pub(crate) fn new(http: Rc<Http>, session: Rc<Session>) -> Self {
         Action {
-            pointer: Vec::new(),
-            keyboard: Vec::new(),
-            wheel: Vec::new(),
+            ticks: Vec::new(),
+            pressed_keys: Vec::new(),
+            pressed_buttons: Vec::new(),
+            interpolation: None,
             session,
             http,
         }
     }
 
-    pub fn clear(mut self) -> Self {
-        self.pointer.clear();
+    /// 执行完后依然按下的键，按照按下的顺序
+    pub fn pressed_keys(&self) -> &[String] {
+        &self.pressed_keys
+    }
+
+    /// 执行完后依然按下的按钮和所在的设备
+    pub fn pressed_buttons(&self) -> &[(String, Button)] {
+        &self.pressed_buttons
+    }
+
+    /// 按相反的顺序松开所有按下的按钮和键
+    pub fn release_all(mut self) -> Self {
+        while let Some((id, button)) = self.pressed_buttons.pop() {
+            let source = self
+                .ticks
+                .iter()
+                .flatten()
+                .find_map(|(s, _)| {
+                    matches!(s, Source::Pointer(i, _) if *i == id).then(|| s.clone())
+                })
+                .unwrap_or_else(Source::mouse);
+            self.ticks
+                .push(vec![(source, Step::Pointer(Pointer::release(button)))]);
+        }
+        while let Some(key) = self.pressed_keys.pop() {
+            self.ticks.push(vec![(
+                Source::Keyboard,
+                Step::Keyboard(Keyboard::key_up(&key)),
+            )]);
+        }
+        self
+    }
+
+    fn track(&mut self, source: &Source, step: &Step) {
+        match (source, step) {
+            (Source::Pointer(id, _), Step::Pointer(pointer)) => {
+                let Some(button) = pointer.button else {
+                    return;
+                };
+                match pointer._type {
+                    ActionType::PointerDown => self.pressed_buttons.push((id.clone(), button)),
+                    ActionType::PointerUp => self
+                        .pressed_buttons
+                        .retain(|(s, b)| !(s == id && *b == button)),
+                    _ => {}
+                }
+            }
+            (_, Step::Keyboard(keyboard)) => {
+                let Some(key) = &keyboard.value else {
+                    return;
+                };
+                match keyboard._type {
+                    ActionType::KeyDown if !self.pressed_keys.contains(key) => {
+                        self.pressed_keys.push(key.clone())
+                    }
+                    ActionType::KeyUp => self.pressed_keys.retain(|k| k != key),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 添加一个只有一个操作的 tick
+    fn push(self, source: Source, step: Step) -> Self {
+        self.push_tick(vec![(source, step)])
+    }
+
+    fn push_tick(mut self, tick: Tick) -> Self {
+        for (source, step) in &tick {
+            self.track(source, step);
+        }
+        self.ticks.push(tick);
         self
     }
 
-    pub fn press(mut self, button: Button) -> Self {
-        self.pointer.push(Pointer::press(button));
+    /// 依次执行指针设备的每个操作
+    pub fn add_source(mut self, source: PointerSource) -> Self {
+        let id = Source::Pointer(source.id, source.kind);
+        for pointer in source.actions {
+            self = self.push(id.clone(), Step::Pointer(pointer));
+        }
         self
     }
 
-    pub fn release(mut self, button: Button) -> Self {
-        self.pointer.push(Pointer::release(button));
+    ///
+    /// 多个指针设备同时执行，每个设备的第 n 个操作在同一个 tick 中开始，比如多指触摸
+    ///
+    /// 设备的名字不能相同，同一个名字在整个操作中只能对应一种类型，否则 [Action::perform] 返回错误
+    pub fn parallel(mut self, sources: impl IntoIterator<Item = PointerSource>) -> Self {
+        let sources: Vec<PointerSource> = sources.into_iter().collect();
+        let n = sources.iter().map(|f| f.actions.len()).max().unwrap_or(0);
+        for i in 0..n {
+            let tick = sources
+                .iter()
+                .filter_map(|f| {
+                    Some((
+                        Source::Pointer(f.id.clone(), f.kind),
+                        Step::Pointer(f.actions.get(i)?.clone()),
+                    ))
+                })
+                .collect();
+            self = self.push_tick(tick);
+        }
         self
     }
 
-    pub fn move_pointer(mut self, element: &Element) -> Self {
-        self.pointer.push(Pointer::move_pointer(element));
+    /// 清空所有操作
+    pub fn clear(mut self) -> Self {
+        self.ticks.clear();
+        self.pressed_keys.clear();
+        self.pressed_buttons.clear();
+        self
+    }
+
+    pub fn press(self, button: Button) -> Self {
+        self.add_pointer(Pointer::press(button))
+    }
+
+    pub fn release(self, button: Button) -> Self {
+        self.add_pointer(Pointer::release(button))
+    }
+
+    pub fn move_pointer(self, element: &Element) -> Self {
+        self.add_pointer(Pointer::move_pointer(element))
+    }
+
+    /// 在 `duration` 毫秒内移动到相对于 `origin` 的 `(x, y)`，元素的坐标相对于元素的中心
+    pub fn move_to(self, origin: impl Into<Origin>, x: i32, y: i32, duration: u32) -> Self {
+        self.add_pointer(Pointer::move_to(origin.into(), x, y, duration))
+    }
+
+    /// 从当前位置移动 `(dx, dy)`
+    pub fn move_by(self, dx: i32, dy: i32) -> Self {
+        self.move_to(Origin::Pointer, dx, dy, DEFAULT_MOVE_DURATION)
+    }
+
+    /// 在元素上按下左键不松开，`None` 表示在当前位置
+    pub fn click_and_hold(self, element: Option<&Element>) -> Self {
+        if let Some(e) = element {
+            self.move_pointer(e)
+        } else {
+            self
+        }
+        .press(Button::LEFT)
+    }
+
+    /// 把 `source` 拖到 `target` 上，使用鼠标事件，不会触发 html5 的 drag 事件
+    pub fn drag_and_drop(self, source: &Element, target: &Element) -> Self {
+        self.click_and_hold(Some(source))
+            .move_pointer(target)
+            .release(Button::LEFT)
+    }
+
+    /// 把 `source` 拖动 `(dx, dy)`，比如滑块
+    pub fn drag_and_drop_by(self, source: &Element, dx: i32, dy: i32) -> Self {
+        self.click_and_hold(Some(source))
+            .move_by(dx, dy)
+            .release(Button::LEFT)
+    }
+
+    ///
+    /// 每次移动拆分成 `steps` 段，依次经过中间的点
+    ///
+    /// 有些滑块和画板只在收到多次 mousemove 时才有反应。
+    /// 需要知道起点，每个设备的第一次移动不拆分；移动到元素时会多一次请求获取元素的位置
+    pub fn interpolate(mut self, steps: u32) -> Self {
+        self.interpolation = (steps > 1).then_some(steps);
         self
     }
 
@@ -481,44 +980,42 @@ impl Action {
         .release(Button::RIGHT)
     }
 
-    pub fn add_pointer(mut self, pointer: Pointer) -> Self {
-        self.pointer.push(pointer);
-        self
+    /// 默认鼠标的操作
+    pub fn add_pointer(self, pointer: Pointer) -> Self {
+        self.push(Source::mouse(), Step::Pointer(pointer))
     }
 
-    pub fn key_down(mut self, key: &str) -> Self {
-        self.keyboard.push(Keyboard::key_down(key));
-        self
+    pub fn add_keyboard(self, keyboard: Keyboard) -> Self {
+        self.push(Source::Keyboard, Step::Keyboard(keyboard))
     }
 
-    pub fn key_up(mut self, key: &str) -> Self {
-        self.keyboard.push(Keyboard::key_up(key));
-        self
+    pub fn key_down(self, key: &str) -> Self {
+        self.add_keyboard(Keyboard::key_down(key))
     }
 
-    pub fn key_down_special(mut self, key: Key) -> Self {
-        self.keyboard.push(Keyboard::key_down(key.as_str()));
-        self
+    pub fn key_up(self, key: &str) -> Self {
+        self.add_keyboard(Keyboard::key_up(key))
     }
 
-    pub fn key_up_special(mut self, key: Key) -> Self {
-        self.keyboard.push(Keyboard::key_up(key.as_str()));
-        self
+    pub fn key_down_special(self, key: Key) -> Self {
+        self.key_down(key.as_str())
     }
 
-    pub fn key_pause(mut self, duration: u32) -> Self {
-        self.keyboard.push(Keyboard::pause(duration));
-        self
+    pub fn key_up_special(self, key: Key) -> Self {
+        self.key_up(key.as_str())
     }
 
-    pub fn mouse_pause(mut self, duration: u32) -> Self {
-        self.pointer.push(Pointer::pause(duration));
-        self
+    pub fn key_pause(self, duration: u32) -> Self {
+        self.add_keyboard(Keyboard::pause(duration))
+    }
+
+    pub fn mouse_pause(self, duration: u32) -> Self {
+        self.add_pointer(Pointer::pause(duration))
     }
 
     /// 鼠标滚轮
     pub fn scroll(
-        mut self,
+        self,
         x: i32,
         y: i32,
         delta_x: i32,
@@ -526,46 +1023,263 @@ impl Action {
         duration: u32,
         origin: Origin,
     ) -> Self {
-        self.wheel.push(
-            Wheel::builder()
-                .r#type(ActionType::Scroll)
-                .x(x)
-                .y(y)
-                .delta_x(delta_x)
-                .delta_y(delta_y)
-                .duration(duration)
-                .origin(origin)
-                .build(),
-        );
-        self
+        self.push(
+            Source::Wheel,
+            Step::Wheel(
+                Wheel::builder()
+                    .r#type(ActionType::Scroll)
+                    .x(x)
+                    .y(y)
+                    .delta_x(delta_x)
+                    .delta_y(delta_y)
+                    .duration(duration)
+                    .origin(origin)
+                    .build(),
+            ),
+        )
     }
 
+    ///
+    /// 执行所有操作
+    ///
+    /// 开启自动等待时，会先等待鼠标移动的目标元素可操作
+    ///
+    /// 发送操作出错时输入状态不确定，会松开所有按下的键和按钮，
+    /// 自动等待等发送之前的错误不会影响之前按下的键
     pub fn perform(&self) -> SResult<()> {
-        let mut req = Vec::new();
-        if !self.pointer.is_empty() {
-            req.push(ActionRequest {
-                actions: self.pointer.iter().map(Device::Pointer).collect(),
-                parameters: Some(Pointer::parameters()),
-                _type: "pointer".to_string(),
-                id: "default mouse".to_string(),
-            });
-        }
-        if !self.keyboard.is_empty() {
-            req.push(ActionRequest {
-                actions: self.keyboard.iter().map(Device::Keyboard).collect(),
-                parameters: None,
-                _type: "key".to_string(),
-                id: "default keyboard".to_string(),
-            });
+        validate(&self.ticks)?;
+        let mut targets: Vec<&Origin> = Vec::new();
+        for (_, step) in self.ticks.iter().flatten() {
+            if let Step::Pointer(Pointer {
+                origin: Some(origin @ Origin::Element(_, id)),
+                ..
+            }) = step
+            {
+                if !targets
+                    .iter()
+                    .any(|t| matches!(t, Origin::Element(_, other) if other == id))
+                {
+                    targets.push(origin);
+                }
+            }
         }
-        if !self.wheel.is_empty() {
-            req.push(ActionRequest {
-                actions: self.wheel.iter().map(Device::Wheel).collect(),
-                parameters: None,
-                _type: "wheel".to_string(),
-                id: "default wheel".to_string(),
-            });
+        actionability::run(&self.http, &self.session, &targets, Checks::POINTER, || {
+            let result = match self.interpolation {
+                Some(steps) => {
+                    let ticks = interpolate(&self.ticks, &self.centers(&targets)?, steps);
+                    self.http
+                        .perform_actions(&self.session.session_id, requests(&ticks))
+                }
+                None => self
+                    .http
+                    .perform_actions(&self.session.session_id, requests(&self.ticks)),
+            };
+            result.inspect_err(|_| {
+                let _ = self.http.release_actions(&self.session.session_id);
+            })
+        })
+    }
+
+    /// 元素在视口中可见部分的中心，和 webdriver 计算元素坐标的方式一致
+    fn centers(&self, targets: &[&Origin]) -> SResult<HashMap<String, (i32, i32)>> {
+        if targets.is_empty() {
+            return Ok(HashMap::new());
         }
-        self.http.perform_actions(&self.session.session_id, req)
+        let centers: Vec<(i32, i32)> = self.http.execute_script_with(
+            &self.session.session_id,
+            CENTER_SCRIPT,
+            vec![serde_json::to_value(targets)?],
+        )?;
+        Ok(targets
+            .iter()
+            .zip(centers)
+            .filter_map(|(origin, center)| match origin {
+                Origin::Element(_, id) => Some((id.clone(), center)),
+                _ => None,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, collections::HashMap, rc::Rc};
+
+    use serde_json::json;
+
+    use crate::{driver::Session, http::Http};
+
+    use super::{
+        interpolate, requests, validate, Action, Button, Key, Origin, Pointer, PointerSource, Step,
+    };
+
+    fn action() -> Action {
+        Action::new(
+            Rc::new(Http::new("http://localhost:4444", 1)),
+            Rc::new(Session {
+                session_id: "test".to_string(),
+                auto_wait: Cell::new(None),
+                script_timeout: Cell::new(None),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_pressed() {
+        let a = action()
+            .key_down_special(Key::Shift)
+            .key_down("a")
+            .key_up("a")
+            .press(Button::LEFT)
+            .add_source(PointerSource::touch("finger").down());
+        assert_eq!(&[Key::Shift.as_str().to_string()], a.pressed_keys());
+        assert_eq!(2, a.pressed_buttons().len());
+
+        let a = a.release_all();
+        assert!(a.pressed_keys().is_empty());
+        assert!(a.pressed_buttons().is_empty());
+        // finger 松开，鼠标松开，shift 松开
+        assert_eq!(8, a.ticks.len());
+        let v = serde_json::to_value(requests(&a.ticks)).unwrap();
+        assert_eq!("finger", v[2]["id"]);
+        assert_eq!(
+            json!({"type": "pointerUp", "button": 0}),
+            v[2]["actions"][5]
+        );
+        assert_eq!(
+            json!({"type": "pointerUp", "button": 0}),
+            v[1]["actions"][6]
+        );
+        assert_eq!(json!({"type": "pause"}), v[1]["actions"][5]);
+        assert_eq!("keyUp", v[0]["actions"][7]["type"]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let a = action().parallel([
+            PointerSource::touch("a").down().up(),
+            PointerSource::touch("b").down().up(),
+        ]);
+        assert!(validate(&a.ticks).is_ok());
+        // 同一个 tick 中重复
+        let a = action().parallel([
+            PointerSource::touch("a").down(),
+            PointerSource::touch("a").down(),
+        ]);
+        assert!(validate(&a.ticks).is_err());
+        // 同一个名字对应不同的类型
+        let a = action()
+            .add_source(PointerSource::touch("a").down().up())
+            .add_source(PointerSource::pen("a").down().up());
+        assert!(validate(&a.ticks).is_err());
+        let a = action()
+            .add_source(PointerSource::mouse("default mouse").down())
+            .press(Button::LEFT);
+        assert!(validate(&a.ticks).is_ok());
+        let a = action()
+            .add_source(PointerSource::touch("default mouse").down())
+            .press(Button::LEFT);
+        assert!(validate(&a.ticks).is_err());
+        assert!(action()
+            .parallel([
+                PointerSource::touch("a").down(),
+                PointerSource::touch("a").down()
+            ])
+            .perform()
+            .is_err());
+    }
+
+    #[test]
+    fn test_timeline() {
+        let a = action()
+            .key_down_special(Key::Control)
+            .press(Button::LEFT)
+            .release(Button::LEFT)
+            .key_up_special(Key::Control)
+            .parallel([
+                PointerSource::touch("a").down().up(),
+                PointerSource::touch("b").down(),
+            ]);
+        let v = serde_json::to_value(requests(&a.ticks)).unwrap();
+        let types = |i: usize| -> Vec<String> {
+            v[i]["actions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|f| f["type"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!("default keyboard", v[0]["id"]);
+        assert_eq!(
+            vec!["keyDown", "pause", "pause", "keyUp", "pause", "pause"],
+            types(0)
+        );
+        assert_eq!("default mouse", v[1]["id"]);
+        assert_eq!(
+            vec![
+                "pause",
+                "pointerDown",
+                "pointerUp",
+                "pause",
+                "pause",
+                "pause"
+            ],
+            types(1)
+        );
+        assert_eq!(json!({"pointerType": "touch"}), v[2]["parameters"]);
+        assert_eq!(
+            vec![
+                "pause",
+                "pause",
+                "pause",
+                "pause",
+                "pointerDown",
+                "pointerUp"
+            ],
+            types(2)
+        );
+        assert_eq!(
+            vec!["pause", "pause", "pause", "pause", "pointerDown", "pause"],
+            types(3)
+        );
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let element = Origin::Element("e".to_string(), "1".to_string());
+        let centers = HashMap::from([("1".to_string(), (100, 50))]);
+        let a = action()
+            .add_pointer(Pointer::move_to(Origin::Pointer, 5, 5, 100))
+            .add_pointer(Pointer::move_to(Origin::Viewport, 0, 0, 0))
+            .key_down("a")
+            .press(Button::LEFT)
+            .add_pointer(Pointer::move_to(element.clone(), 0, 10, 400))
+            .add_pointer(Pointer::move_to(Origin::Pointer, -40, 0, 100));
+        let ticks = interpolate(&a.ticks, &centers, 4);
+        let points: Vec<_> = ticks
+            .iter()
+            .map(|tick| match &tick[..] {
+                [(_, Step::Pointer(f))] => (f.origin.clone(), f.x, f.y, f.duration),
+                _ => (None, None, None, None),
+            })
+            .collect();
+        let viewport = |x, y, d| (Some(Origin::Viewport), Some(x), Some(y), Some(d));
+        assert_eq!(
+            vec![
+                (Some(Origin::Pointer), Some(5), Some(5), Some(100)),
+                viewport(0, 0, 0),
+                (None, None, None, None),
+                (None, None, None, None),
+                viewport(25, 15, 100),
+                viewport(50, 30, 100),
+                viewport(75, 45, 100),
+                (Some(element), Some(0), Some(10), Some(100)),
+                viewport(90, 60, 25),
+                viewport(80, 60, 25),
+                viewport(70, 60, 25),
+                viewport(60, 60, 25),
+            ],
+            points
+        );
     }
 }
@@ -0,0 +1,261 @@
+//!
+//! css 选择器相关的转义
+//!
+//! [escape] 按照 [CSS.escape](https://drafts.csswg.org/cssom/#the-css.escape()-method) 的规则转义标识符，
+//! [quote] 生成带双引号的 css 字符串，用于属性值
+
+use std::fmt::Display;
+
+use crate::{driver::ByBuf, By};
+
+/// 转义 id、class、属性名等标识符
+pub fn escape(ident: &str) -> String {
+    let mut s = String::with_capacity(ident.len());
+    let chars: Vec<char> = ident.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            '\0' => s.push('\u{FFFD}'),
+            '\u{1}'..='\u{1f}' | '\u{7f}' => push_code_point(&mut s, c),
+            '0'..='9' if i == 0 || (i == 1 && chars[0] == '-') => push_code_point(&mut s, c),
+            '-' if i == 0 && chars.len() == 1 => s.push_str("\\-"),
+            c if c >= '\u{80}' || c == '-' || c == '_' || c.is_ascii_alphanumeric() => s.push(c),
+            c => {
+                s.push('\\');
+                s.push(c);
+            }
+        }
+    }
+    s
+}
+
+/// 转义后加上双引号，用于 `[name="value"]` 中的属性值
+pub fn quote(value: &str) -> String {
+    let mut s = String::with_capacity(value.len() + 2);
+    s.push('"');
+    for c in value.chars() {
+        match c {
+            '\0' => s.push('\u{FFFD}'),
+            '\u{1}'..='\u{1f}' | '\u{7f}' => push_code_point(&mut s, c),
+            '"' | '\\' => {
+                s.push('\\');
+                s.push(c);
+            }
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+    s
+}
+
+///
+/// 可以直接插入选择器的整数，`css!`、`xpath!` 中只接受整数的占位符需要实现
+pub trait Integer: Display {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {})*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T: Integer + ?Sized> Integer for &T {}
+
+/// 用于 `:nth-child()` 等的参数
+pub fn number<T: Integer>(value: T) -> String {
+    value.to_string()
+}
+
+/// `\` 加上十六进制码点，后面的空格用来结束转义
+fn push_code_point(s: &mut String, c: char) {
+    s.push_str(&format!("\\{:x} ", c as u32));
+}
+
+///
+/// 类型化的 css 选择器，所有的值都会转义
+///
+/// ```
+/// use selenium::css::Css;
+/// let css = Css::tag("button")
+///     .class("primary")
+///     .attr("type", "submit")
+///     .descendant(Css::new().class("icon"));
+/// assert_eq!(r#"button.primary[type="submit"] .icon"#, css.to_string());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Css {
+    selector: String,
+    /// 最后一个复合选择器的开始位置
+    start: usize,
+}
+
+impl Default for Css {
+    fn default() -> Self {
+        Css::new()
+    }
+}
+
+impl Css {
+    /// 匹配任意元素，等同于 `*`
+    pub fn new() -> Self {
+        Css {
+            selector: "*".to_string(),
+            start: 0,
+        }
+    }
+
+    pub fn tag(tag: &str) -> Self {
+        Css {
+            selector: escape(tag),
+            start: 0,
+        }
+    }
+
+    pub fn id(self, id: &str) -> Self {
+        self.push(&format!("#{}", escape(id)))
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        self.push(&format!(".{}", escape(class)))
+    }
+
+    /// 存在属性
+    pub fn has_attr(self, name: &str) -> Self {
+        self.push(&format!("[{}]", escape(name)))
+    }
+
+    /// 属性值相等
+    pub fn attr(self, name: &str, value: &str) -> Self {
+        self.attr_with("=", name, value)
+    }
+
+    /// 属性值包含
+    pub fn attr_contains(self, name: &str, value: &str) -> Self {
+        self.attr_with("*=", name, value)
+    }
+
+    pub fn attr_starts_with(self, name: &str, value: &str) -> Self {
+        self.attr_with("^=", name, value)
+    }
+
+    pub fn attr_ends_with(self, name: &str, value: &str) -> Self {
+        self.attr_with("$=", name, value)
+    }
+
+    fn attr_with(self, operator: &str, name: &str, value: &str) -> Self {
+        self.push(&format!("[{}{operator}{}]", escape(name), quote(value)))
+    }
+
+    /// 从1开始
+    pub fn nth_child(self, n: usize) -> Self {
+        self.push(&format!(":nth-child({n})"))
+    }
+
+    pub fn not(self, other: Css) -> Self {
+        self.push(&format!(":not({other})"))
+    }
+
+    /// 后代元素，`a b`
+    pub fn descendant(self, other: Css) -> Self {
+        self.combine(" ", other)
+    }
+
+    /// 子元素，`a > b`
+    pub fn child(self, other: Css) -> Self {
+        self.combine(" > ", other)
+    }
+
+    /// 紧邻的下一个兄弟元素，`a + b`
+    pub fn next_sibling(self, other: Css) -> Self {
+        self.combine(" + ", other)
+    }
+
+    /// 之后的兄弟元素，`a ~ b`
+    pub fn subsequent_sibling(self, other: Css) -> Self {
+        self.combine(" ~ ", other)
+    }
+
+    /// 追加到最后一个复合选择器，单独的 `*` 可以省略
+    fn push(mut self, simple: &str) -> Self {
+        if &self.selector[self.start..] == "*" {
+            self.selector.truncate(self.start);
+        }
+        self.selector.push_str(simple);
+        self
+    }
+
+    fn combine(mut self, combinator: &str, other: Css) -> Self {
+        self.selector.push_str(combinator);
+        self.start = self.selector.len() + other.start;
+        self.selector.push_str(&other.selector);
+        self
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.selector
+    }
+
+    pub fn as_by(&self) -> By<'_> {
+        By::Css(&self.selector)
+    }
+}
+
+impl Display for Css {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.selector)
+    }
+}
+
+impl From<Css> for ByBuf {
+    fn from(value: Css) -> Self {
+        ByBuf::Css(value.selector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape, quote, Css};
+
+    #[test]
+    fn test_escape() {
+        assert_eq!("name", escape("name"));
+        assert_eq!("\\31 23", escape("123"));
+        assert_eq!("-\\31 a", escape("-1a"));
+        assert_eq!("\\-", escape("-"));
+        assert_eq!("--a", escape("--a"));
+        assert_eq!("form\\:input", escape("form:input"));
+        assert_eq!("a\\.b", escape("a.b"));
+        assert_eq!("a\\ b", escape("a b"));
+        assert_eq!("\\9 a", escape("\ta"));
+        assert_eq!("中文", escape("中文"));
+        assert_eq!("\u{FFFD}", escape("\0"));
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(r#""value""#, quote("value"));
+        assert_eq!(r#""a\"b\\c""#, quote(r#"a"b\c"#));
+        assert_eq!("\"a\\a b\"", quote("a\nb"));
+    }
+
+    #[test]
+    fn test_builder() {
+        assert_eq!("*", Css::new().to_string());
+        assert_eq!(
+            "#\\31 .a\\:b[data-id*=\"x\\\"y\"]:nth-child(2)",
+            Css::new()
+                .id("1")
+                .class("a:b")
+                .attr_contains("data-id", "x\"y")
+                .nth_child(2)
+                .to_string()
+        );
+        assert_eq!(
+            "form > * input:not(.hidden) + label",
+            Css::tag("form")
+                .child(Css::new().descendant(Css::tag("input").not(Css::new().class("hidden"))))
+                .next_sibling(Css::tag("label"))
+                .to_string()
+        );
+    }
+}
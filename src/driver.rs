@@ -1,4 +1,5 @@
 use std::{
+    cell::Cell,
     collections::HashMap,
     fmt::Display,
     io::{BufRead, BufReader, Read},
@@ -12,16 +13,35 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     actions::Action,
+    css::{self, Css},
     element::Element,
+    frame::{self, FramePath, FrameRef},
     http::{Capability, Http},
+    inspect::{self, Field, Inspection, Snapshot},
+    locator::Locator,
     option::{Browser, BrowserOption},
-    SError, SResult,
+    relative::{RelativeBy, RELATIVE_SCRIPT},
+    wait::{quiescence::QuiescenceProbe, Wait, DEFAULT_POLLING, NETWORK_SCRIPT, OBSERVE_SCRIPT},
+    ErrorKind, SError, SResult,
 };
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct Session {
     pub(crate) session_id: String,
+    /// 自动等待元素可操作的超时时间
+    #[serde(skip)]
+    pub(crate) auto_wait: Cell<Option<Duration>>,
+    /// 脚本超时时间，毫秒，创建会话时从 capabilities 中读取，[Driver::set_timeouts] 时更新
+    #[serde(default, rename = "capabilities", deserialize_with = "script_timeout")]
+    pub(crate) script_timeout: Cell<Option<u32>>,
+}
+
+fn script_timeout<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Cell<Option<u32>>, D::Error> {
+    let v = serde_json::Value::deserialize(d)?;
+    Ok(Cell::new(
+        v["timeouts"]["script"].as_u64().map(|f| f as u32),
+    ))
 }
 
 struct DriverProcess {
@@ -205,14 +225,93 @@ pub enum TimeoutType {
     Implicit(u32),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum By<'a> {
     Css(&'a str),
     LinkText(&'a str),
     ParitialLinkText(&'a str),
     TagName(&'a str),
     XPath(&'a str),
+    /// 会按照 css 标识符的规则转义，可以直接使用以数字开头或者包含 `:`、`.` 的 class
     Class(&'a str),
+    /// 会按照 css 标识符的规则转义，可以直接使用以数字开头或者包含 `:`、`.` 的 id
     Id(&'a str),
+    /// name 属性
+    Name(&'a str),
+    /// 属性名和属性值完全相等
+    Attribute(&'a str, &'a str),
+    /// 可见文字完全相等，文字中的空白会合并为一个空格
+    Text(TextQuery<'a>),
+    /// 可见文字包含
+    TextContains(TextQuery<'a>),
+    /// 可见文字匹配正则表达式，使用 js 的 RegExp
+    TextRegex(TextQuery<'a>),
+    ///
+    /// 依次尝试，返回第一个找到元素的查找方式的结果
+    ///
+    /// 用到了后面的备选方式时会通过 `log::warn!` 输出，方便及时修复失效的选择器
+    ///
+    /// 通过 [By::any_of] 或者 [ByBuf::AnyOf] 创建
+    AnyOf(AnyOf<'a>),
+}
+
+/// [By::AnyOf] 中的备选方式
+#[derive(Clone, Copy, Debug)]
+pub struct AnyOf<'a>(Alternatives<'a>);
+
+#[derive(Clone, Copy, Debug)]
+enum Alternatives<'a> {
+    By(&'a [By<'a>]),
+    Buf(&'a [ByBuf]),
+}
+
+impl<'a> AnyOf<'a> {
+    fn list(self) -> Vec<By<'a>> {
+        match self.0 {
+            Alternatives::By(list) => list.to_vec(),
+            Alternatives::Buf(list) => list.iter().map(|f| f.as_by()).collect(),
+        }
+    }
+}
+
+impl PartialEq for AnyOf<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.list() == other.list()
+    }
+}
+
+impl Eq for AnyOf<'_> {}
+
+impl std::hash::Hash for AnyOf<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.list().hash(state)
+    }
+}
+
+/// 按可见文字查找，由注入的脚本实现，只返回可见且最内层的匹配元素
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TextQuery<'a> {
+    pub value: &'a str,
+    pub ignore_case: bool,
+}
+
+impl<'a> TextQuery<'a> {
+    /// 忽略大小写，比如 `By::text_contains(TextQuery::ignore_case("save"))`
+    pub fn ignore_case(value: &'a str) -> Self {
+        TextQuery {
+            value,
+            ignore_case: true,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for TextQuery<'a> {
+    fn from(value: &'a str) -> Self {
+        TextQuery {
+            value,
+            ignore_case: false,
+        }
+    }
 }
 
 impl<'a> AsRef<By<'a>> for By<'a> {
@@ -221,6 +320,257 @@ impl<'a> AsRef<By<'a>> for By<'a> {
     }
 }
 
+impl<'a> By<'a> {
+    /// 参数可以是 `&str` 或者 [TextQuery]
+    pub fn text(value: impl Into<TextQuery<'a>>) -> Self {
+        By::Text(value.into())
+    }
+
+    pub fn text_contains(value: impl Into<TextQuery<'a>>) -> Self {
+        By::TextContains(value.into())
+    }
+
+    pub fn text_regex(value: impl Into<TextQuery<'a>>) -> Self {
+        By::TextRegex(value.into())
+    }
+
+    pub fn any_of(list: &'a [By<'a>]) -> Self {
+        By::AnyOf(AnyOf(Alternatives::By(list)))
+    }
+
+    /// [By::AnyOf] 中的备选方式
+    pub(crate) fn alternatives(&self) -> Option<Vec<By<'_>>> {
+        match self {
+            By::AnyOf(list) => Some(list.list()),
+            _ => None,
+        }
+    }
+
+    /// 需要注入脚本查找时，返回脚本参数
+    pub(crate) fn script_query(&self) -> Option<serde_json::Value> {
+        let (kind, q) = match self {
+            By::Text(q) => ("exact", q),
+            By::TextContains(q) => ("contains", q),
+            By::TextRegex(q) => ("regex", q),
+            _ => return None,
+        };
+        Some(serde_json::json!({
+            "kind": kind,
+            "value": q.value,
+            "ignore_case": q.ignore_case,
+        }))
+    }
+
+    /// 协议中的 `using` 和 `value`，协议不支持的查找方式返回 `None`
+    pub(crate) fn selector(&self) -> Option<(&'static str, String)> {
+        Some(match self {
+            By::Css(v) => ("css selector", v.to_string()),
+            By::LinkText(v) => ("link text", v.to_string()),
+            By::ParitialLinkText(v) => ("partial link text", v.to_string()),
+            By::TagName(v) => ("tag name", v.to_string()),
+            By::XPath(v) => ("xpath", v.to_string()),
+            By::Class(v) => ("css selector", format!(".{}", css::escape(v))),
+            By::Id(v) => ("css selector", format!("#{}", css::escape(v))),
+            By::Name(v) => ("css selector", format!("[name={}]", css::quote(v))),
+            By::Attribute(name, v) => (
+                "css selector",
+                format!("[{}={}]", css::escape(name), css::quote(v)),
+            ),
+            By::Text(_) | By::TextContains(_) | By::TextRegex(_) | By::AnyOf(_) => return None,
+        })
+    }
+}
+
+impl Display for By<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            By::Css(v) => write!(f, "css={v}"),
+            By::LinkText(v) => write!(f, "link_text={v}"),
+            By::ParitialLinkText(v) => write!(f, "partial_link_text={v}"),
+            By::TagName(v) => write!(f, "tag_name={v}"),
+            By::XPath(v) => write!(f, "xpath={v}"),
+            By::Class(v) => write!(f, "class={v}"),
+            By::Id(v) => write!(f, "id={v}"),
+            By::Name(v) => write!(f, "name={v}"),
+            By::Attribute(name, v) => write!(f, "attribute={name}={v}"),
+            By::Text(q) => write!(f, "text={q}"),
+            By::TextContains(q) => write!(f, "text_contains={q}"),
+            By::TextRegex(q) => write!(f, "text_regex={q}"),
+            By::AnyOf(_) => {
+                f.write_str("any_of=[")?;
+                for (i, by) in self.alternatives().unwrap_or_default().iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{by}")?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+impl Display for TextQuery<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.value)?;
+        if self.ignore_case {
+            f.write_str(" (ignore case)")?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// 可以作为查找方式使用的类型，比如 [By]、[ByBuf] 和 [Css](crate::css::Css)
+pub trait AsBy {
+    fn as_by(&self) -> By<'_>;
+}
+
+impl AsBy for By<'_> {
+    fn as_by(&self) -> By<'_> {
+        *self
+    }
+}
+
+impl AsBy for ByBuf {
+    fn as_by(&self) -> By<'_> {
+        ByBuf::as_by(self)
+    }
+}
+
+/// 字符串作为 css 选择器
+impl AsBy for str {
+    fn as_by(&self) -> By<'_> {
+        By::Css(self)
+    }
+}
+
+impl AsBy for String {
+    fn as_by(&self) -> By<'_> {
+        By::Css(self)
+    }
+}
+
+impl AsBy for Css {
+    fn as_by(&self) -> By<'_> {
+        Css::as_by(self)
+    }
+}
+
+impl<T: AsBy + ?Sized> AsBy for &T {
+    fn as_by(&self) -> By<'_> {
+        T::as_by(self)
+    }
+}
+
+///
+/// 拥有所有权的 [By]，可以保存在结构体、`HashMap` 中或者从配置文件中读取
+///
+/// ```
+/// use selenium::driver::ByBuf;
+/// let by: ByBuf = serde_json::from_str(r#"{"attribute":["data-testid","submit"]}"#).unwrap();
+/// assert_eq!(ByBuf::Attribute("data-testid".to_string(), "submit".to_string()), by);
+/// assert_eq!("attribute=data-testid=submit", by.to_string());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByBuf {
+    Css(String),
+    LinkText(String),
+    #[serde(rename = "partial_link_text")]
+    ParitialLinkText(String),
+    TagName(String),
+    #[serde(rename = "xpath")]
+    XPath(String),
+    Class(String),
+    Id(String),
+    Name(String),
+    Attribute(String, String),
+    Text {
+        value: String,
+        #[serde(default)]
+        ignore_case: bool,
+    },
+    TextContains {
+        value: String,
+        #[serde(default)]
+        ignore_case: bool,
+    },
+    TextRegex {
+        value: String,
+        #[serde(default)]
+        ignore_case: bool,
+    },
+    AnyOf(Vec<ByBuf>),
+}
+
+impl ByBuf {
+    pub fn as_by(&self) -> By<'_> {
+        match self {
+            ByBuf::Css(v) => By::Css(v),
+            ByBuf::LinkText(v) => By::LinkText(v),
+            ByBuf::ParitialLinkText(v) => By::ParitialLinkText(v),
+            ByBuf::TagName(v) => By::TagName(v),
+            ByBuf::XPath(v) => By::XPath(v),
+            ByBuf::Class(v) => By::Class(v),
+            ByBuf::Id(v) => By::Id(v),
+            ByBuf::Name(v) => By::Name(v),
+            ByBuf::Attribute(name, v) => By::Attribute(name, v),
+            ByBuf::Text { value, ignore_case } => By::Text(TextQuery {
+                value,
+                ignore_case: *ignore_case,
+            }),
+            ByBuf::TextContains { value, ignore_case } => By::TextContains(TextQuery {
+                value,
+                ignore_case: *ignore_case,
+            }),
+            ByBuf::TextRegex { value, ignore_case } => By::TextRegex(TextQuery {
+                value,
+                ignore_case: *ignore_case,
+            }),
+            ByBuf::AnyOf(list) => By::AnyOf(AnyOf(Alternatives::Buf(list))),
+        }
+    }
+}
+
+impl From<By<'_>> for ByBuf {
+    fn from(value: By<'_>) -> Self {
+        match value {
+            By::Css(v) => ByBuf::Css(v.to_string()),
+            By::LinkText(v) => ByBuf::LinkText(v.to_string()),
+            By::ParitialLinkText(v) => ByBuf::ParitialLinkText(v.to_string()),
+            By::TagName(v) => ByBuf::TagName(v.to_string()),
+            By::XPath(v) => ByBuf::XPath(v.to_string()),
+            By::Class(v) => ByBuf::Class(v.to_string()),
+            By::Id(v) => ByBuf::Id(v.to_string()),
+            By::Name(v) => ByBuf::Name(v.to_string()),
+            By::Attribute(name, v) => ByBuf::Attribute(name.to_string(), v.to_string()),
+            By::Text(q) => ByBuf::Text {
+                value: q.value.to_string(),
+                ignore_case: q.ignore_case,
+            },
+            By::TextContains(q) => ByBuf::TextContains {
+                value: q.value.to_string(),
+                ignore_case: q.ignore_case,
+            },
+            By::TextRegex(q) => ByBuf::TextRegex {
+                value: q.value.to_string(),
+                ignore_case: q.ignore_case,
+            },
+            By::AnyOf(AnyOf(Alternatives::By(list))) => {
+                ByBuf::AnyOf(list.iter().map(|f| ByBuf::from(*f)).collect())
+            }
+            By::AnyOf(AnyOf(Alternatives::Buf(list))) => ByBuf::AnyOf(list.to_vec()),
+        }
+    }
+}
+
+impl Display for ByBuf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.as_by(), f)
+    }
+}
+
 #[derive(Deserialize, Clone, Serialize, Debug)]
 pub struct Rect {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -365,12 +715,34 @@ impl Driver {
     }
 
     pub fn set_timeouts(&self, timeout: TimeoutType) -> SResult<()> {
-        self.http.set_timeouts(&self.session.session_id, timeout)
+        let script = match timeout {
+            TimeoutType::Script(ms) => Some(ms),
+            _ => None,
+        };
+        self.http.set_timeouts(&self.session.session_id, timeout)?;
+        if script.is_some() {
+            self.session.script_timeout.set(script);
+        }
+        Ok(())
     }
 
     pub fn get_timeouts(&self) -> SResult<Vec<TimeoutType>> {
         self.http.get_timouts(&self.session.session_id)
     }
+
+    ///
+    /// 开启后 [Element::click]、[Element::clear]、[Element::send_keys] 和 [Action::perform]
+    /// 会先等待元素可操作，`None` 关闭
+    ///
+    /// 可操作指元素仍在文档中、可见、可用、连续两帧位置不变并且中心点没有被遮挡，
+    /// 输入类操作只检查前三项
+    pub fn set_auto_wait(&self, timeout: Option<Duration>) {
+        self.session.auto_wait.set(timeout);
+    }
+
+    pub fn get_auto_wait(&self) -> Option<Duration> {
+        self.session.auto_wait.get()
+    }
 }
 /// contenxts
 impl Driver {
@@ -397,6 +769,73 @@ impl Driver {
     pub fn switch_to_frame(&self, id: SwitchToFrame) -> SResult<()> {
         self.http.switch_to_frame(&self.session.session_id, id)
     }
+    /// 回到顶层文档后依次进入 `path` 中的 frame
+    pub fn switch_to_frame_path(&self, path: &FramePath) -> SResult<()> {
+        frame::enter(&self.http, &self.session, path)
+    }
+
+    ///
+    /// 进入 `path` 中的 frame 执行 `f`，结束后无论成功与否都回到顶层文档
+    ///
+    /// `f` 中得到的元素只在该 frame 中有效，不要在 `f` 之外使用
+    pub fn within_frames<T>(
+        &self,
+        path: &FramePath,
+        f: impl FnOnce(&Driver) -> SResult<T>,
+    ) -> SResult<T> {
+        frame::within(&self.http, &self.session, path, || f(self))
+    }
+
+    ///
+    /// 从顶层文档开始，在所有嵌套的 frame 中深度优先查找，返回元素和所在的 frame 路径
+    ///
+    /// 找到后停留在元素所在的 frame 中，可以直接操作返回的元素，之后需要自己切换回去；
+    /// 找不到时回到顶层文档。路径中的每一层 frame 都用 [Element::unique_selector] 定位，
+    /// 可以用 [Driver::within_frames] 或 [Driver::switch_to_frame_path] 重新进入
+    pub fn find_in_all_frames<T: AsBy>(&self, by: T) -> SResult<(Element, FramePath)> {
+        self.switch_to_frame(SwitchToFrame::Null)?;
+        let mut path = FramePath::new();
+        let found = self.search_frames(&by.as_by(), &mut path);
+        match found {
+            Ok(Some(element)) => Ok((element, path)),
+            Ok(None) => {
+                self.switch_to_frame(SwitchToFrame::Null)?;
+                Err(SError::no_such_element())
+            }
+            Err(e) => {
+                self.switch_to_frame(SwitchToFrame::Null)?;
+                Err(e)
+            }
+        }
+    }
+
+    fn search_frames(&self, by: &By<'_>, path: &mut FramePath) -> SResult<Option<Element>> {
+        match self.find_element(by) {
+            Ok(element) => return Ok(Some(element)),
+            Err(e) if frame::is_skippable(e.kind()) => {}
+            Err(e) => return Err(e),
+        }
+        for element in self.find_elements(By::Css(frame::FRAME_SELECTOR))? {
+            let selector = match element.unique_selector() {
+                Ok(selector) => selector,
+                Err(e) if frame::is_skippable(e.kind()) => continue,
+                Err(e) => return Err(e),
+            };
+            match self.switch_to_frame(SwitchToFrame::Element(element.id.clone())) {
+                Ok(()) => {}
+                Err(e) if frame::is_skippable(e.kind()) => continue,
+                Err(e) => return Err(e),
+            }
+            path.push(FrameRef::By(selector.as_by().into()));
+            if let Some(element) = self.search_frames(by, path)? {
+                return Ok(Some(element));
+            }
+            path.pop();
+            self.switch_to_parent_frame()?;
+        }
+        Ok(None)
+    }
+
     ///
     /// https://w3c.github.io/webdriver/#switch-to-parent-frame
     pub fn switch_to_parent_frame(&self) -> SResult<()> {
@@ -426,10 +865,10 @@ impl Driver {
 
 /// element
 impl Driver {
-    pub fn find_element<'a, T: AsRef<By<'a>>>(&self, by: T) -> SResult<Element> {
+    pub fn find_element<T: AsBy>(&self, by: T) -> SResult<Element> {
         let v = self
             .http
-            .find_element(&self.session.session_id, by.as_ref())?;
+            .find_element(&self.session.session_id, &by.as_by())?;
         Ok(Element {
             http: Rc::clone(&self.http),
             session: Rc::clone(&self.session),
@@ -438,10 +877,10 @@ impl Driver {
         })
     }
 
-    pub fn find_elements<'a, T: AsRef<By<'a>>>(&self, by: T) -> SResult<Vec<Element>> {
+    pub fn find_elements<T: AsBy>(&self, by: T) -> SResult<Vec<Element>> {
         let v = self
             .http
-            .find_elements(&self.session.session_id, by.as_ref())?;
+            .find_elements(&self.session.session_id, &by.as_by())?;
         Ok(v.iter()
             .map(|f| Element {
                 http: Rc::clone(&self.http),
@@ -452,6 +891,43 @@ impl Driver {
             .collect())
     }
 
+    /// 相对定位查找元素，返回距离第一个参照元素最近的那个
+    pub fn find_element_relative(&self, by: &RelativeBy<'_>) -> SResult<Element> {
+        self.find_elements_relative(by)?
+            .into_iter()
+            .next()
+            .ok_or_else(SError::no_such_element)
+    }
+
+    /// 相对定位查找元素，按照与第一个参照元素的距离由近到远排列
+    ///
+    /// 候选元素通过 [Driver::find_elements] 查找，支持所有查找方式
+    pub fn find_elements_relative(&self, by: &RelativeBy<'_>) -> SResult<Vec<Element>> {
+        let candidates = self.find_elements(by.root)?;
+        if candidates.is_empty() {
+            return Ok(candidates);
+        }
+        let v: Vec<HashMap<String, String>> = self.http.execute_script_with(
+            &self.session.session_id,
+            RELATIVE_SCRIPT,
+            by.args(&candidates)?,
+        )?;
+        Ok(v.into_iter()
+            .filter_map(|f| f.into_iter().next())
+            .map(|f| Element {
+                http: Rc::clone(&self.http),
+                session: Rc::clone(&self.session),
+                identify: f.0,
+                id: f.1,
+            })
+            .collect())
+    }
+
+    /// 延迟查找，每次操作时才查找元素，见 [Locator]
+    pub fn locator<T: AsBy>(&self, by: T) -> Locator {
+        Locator::new(Rc::clone(&self.http), Rc::clone(&self.session), by.as_by())
+    }
+
     pub fn get_active_element(&self) -> SResult<Element> {
         let v = self.http.get_active_element(&self.session.session_id)?;
         Ok(Element {
@@ -512,6 +988,40 @@ impl Driver {
             .execute_async_script::<T>(&self.session.session_id, script, args)
     }
 
+    ///
+    /// 参数列表是元组或者切片，每一项是可以序列化的值，[Element] 会作为元素引用传入
+    ///
+    /// 只有一个参数时写成 `(arg,)`，比如数组作为一个参数时是 `(vec![1, 2, 3],)`，没有参数时是 `()`
+    ///
+    /// ```no_run
+    /// use selenium::option::FirefoxBuilder;
+    /// use selenium::driver::Driver;
+    /// use selenium::By;
+    /// let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+    /// let element = driver.find_element(By::Id("name")).unwrap();
+    /// let tag: String = driver
+    ///     .execute_script_with("return arguments[0].tagName + arguments[1]", (&element, 1))
+    ///     .unwrap();
+    /// ```
+    pub fn execute_script_with<T: serde::de::DeserializeOwned, A: ScriptArgs>(
+        &self,
+        script: &str,
+        args: A,
+    ) -> SResult<T> {
+        self.http
+            .execute_script_with(&self.session.session_id, script, args.into_args()?)
+    }
+
+    /// 同 [Driver::execute_script_with]
+    pub fn execute_async_script_with<T: serde::de::DeserializeOwned, A: ScriptArgs>(
+        &self,
+        script: &str,
+        args: A,
+    ) -> SResult<T> {
+        self.http
+            .execute_async_script_with(&self.session.session_id, script, args.into_args()?)
+    }
+
     pub fn dismiss_alert(&self) -> SResult<()> {
         self.http.dismiss_alert(&self.session.session_id)
     }
@@ -537,54 +1047,135 @@ impl Driver {
     }
 }
 
+///
+/// 脚本的参数列表，见 [Driver::execute_script_with]
+///
+/// 为 `()`、最多 6 项的元组以及 `&[T]`、`Vec<T>` 实现，每一项是一个参数
+pub trait ScriptArgs {
+    fn into_args(self) -> SResult<Vec<serde_json::Value>>;
+}
+
+impl ScriptArgs for () {
+    fn into_args(self) -> SResult<Vec<serde_json::Value>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<T: Serialize> ScriptArgs for &[T] {
+    fn into_args(self) -> SResult<Vec<serde_json::Value>> {
+        self.iter().map(|f| Ok(serde_json::to_value(f)?)).collect()
+    }
+}
+
+impl<T: Serialize> ScriptArgs for Vec<T> {
+    fn into_args(self) -> SResult<Vec<serde_json::Value>> {
+        self.as_slice().into_args()
+    }
+}
+
+macro_rules! tuple_args {
+    ($($name:ident),+) => {
+        impl<$($name: Serialize),+> ScriptArgs for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_args(self) -> SResult<Vec<serde_json::Value>> {
+                let ($($name,)+) = self;
+                Ok(vec![$(serde_json::to_value($name)?),+])
+            }
+        }
+    };
+}
+
+tuple_args!(A);
+tuple_args!(A, B);
+tuple_args!(A, B, C);
+tuple_args!(A, B, C, D);
+tuple_args!(A, B, C, D, E);
+tuple_args!(A, B, C, D, E, F);
+
+// 批量读取
+impl Driver {
+    ///
+    /// 一次脚本调用读取多个元素的多个字段，结果和 `elements` 一一对应
+    ///
+    /// 字段的写法参考 [Field](crate::inspect::Field)，比如 `text`、`attr:href`、`rect`、`displayed`
+    pub fn inspect<F: AsRef<str>>(
+        &self,
+        elements: &[Element],
+        fields: &[F],
+    ) -> SResult<Vec<Inspection>> {
+        let fields = fields
+            .iter()
+            .map(|f| f.as_ref().parse())
+            .collect::<SResult<Vec<Field>>>()?;
+        inspect::inspect(elements, &fields)
+    }
+
+    /// 同 [Element::snapshot]，一次读取所有元素
+    pub fn snapshots(&self, elements: &[Element]) -> SResult<Vec<Snapshot>> {
+        inspect::inspect(elements, &Snapshot::FIELDS)
+    }
+}
+
 impl Driver {
     pub fn actions(&self) -> Action {
         Action::new(Rc::clone(&self.http), Rc::clone(&self.session))
     }
+
+    /// 松开所有按下的键和鼠标按钮
+    pub fn release_actions(&self) -> SResult<()> {
+        self.http.release_actions(&self.session.session_id)
+    }
+
+    ///
+    /// 执行 `f`，结束后无论成功与否都松开所有按下的键和按钮
+    ///
+    /// `f` 出错时返回 `f` 的错误
+    ///
+    /// ```no_run
+    /// use selenium::option::FirefoxBuilder;
+    /// use selenium::driver::Driver;
+    /// use selenium::{By, Key};
+    /// let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+    /// driver
+    ///     .with_actions(|d| {
+    ///         d.actions().key_down_special(Key::Shift).perform()?;
+    ///         d.find_element(By::Id("item"))?.click()
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn with_actions<T>(&self, f: impl FnOnce(&Driver) -> SResult<T>) -> SResult<T> {
+        let result = f(self);
+        let release = self.release_actions();
+        let v = result?;
+        release?;
+        Ok(v)
+    }
 }
 
 // Wait
 impl Driver {
+    /// 显式等待，参考 [Wait]
+    pub fn wait(&self) -> Wait<'_> {
+        Wait::new(self)
+    }
+
+    fn wait_millis(&self, timeout: u128) -> Wait<'_> {
+        self.wait()
+            .timeout(Duration::from_millis(timeout.min(u64::MAX as u128) as u64))
+    }
+
     /// 等待元素不存在
     ///
     /// # Params
     ///
     /// timeout 毫秒
     pub fn wait_until_element_not_exist(&self, by: By<'_>, timeout: u128) -> SResult<()> {
-        let s = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|v| v.as_millis())
-            .unwrap_or(0);
-
-        let mut count = 0;
-        loop {
-            count += 1;
-            let n = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|v| v.as_millis())
-                .unwrap_or(0);
-            if n - s > timeout {
-                return Err(SError::Timeout("wait timeout".to_string()));
-            }
-
-            match self.find_element(by.as_ref()) {
-                Ok(ele) => {
-                    continue;
-                }
-                Err(SError::Http(ref status, msg)) if *status == 404 => {
-                    return Ok(());
-                }
-                Err(e) => {
-                    let n = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|v| v.as_millis())
-                        .unwrap_or(0);
-                    if n - s > timeout {
-                        return Err(SError::Timeout(format!("{:?}", e)));
-                    }
-                }
-            }
-        }
+        self.wait_millis(timeout)
+            .until(|driver| match driver.find_element(by) {
+                Ok(_) => Ok(None),
+                Err(e) if e.kind() == ErrorKind::NoSuchElement => Ok(Some(())),
+                Err(_) => Ok(None),
+            })
     }
 
     /// 等待直到元素出现
@@ -593,37 +1184,8 @@ impl Driver {
     ///
     /// timeout 毫秒
     pub fn wait_until_element(&self, by: By<'_>, timeout: u128) -> SResult<Element> {
-        let s = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|v| v.as_millis())
-            .unwrap_or(0);
-
-        let mut count = 0;
-        loop {
-            count += 1;
-            let n = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|v| v.as_millis())
-                .unwrap_or(0);
-            if n - s > timeout {
-                return Err(SError::Timeout("wait timeout".to_string()));
-            }
-
-            match self.find_element(by.as_ref()) {
-                Ok(ele) => {
-                    return Ok(ele);
-                }
-                Err(e) => {
-                    let n = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|v| v.as_millis())
-                        .unwrap_or(0);
-                    if n - s > timeout {
-                        return Err(SError::Timeout(format!("{:?}", e)));
-                    }
-                }
-            }
-        }
+        self.wait_millis(timeout)
+            .until(|driver| Ok(driver.find_element(by).ok()))
     }
 
     /// # Params
@@ -635,37 +1197,12 @@ impl Driver {
         timeout: u128,
         on: T,
     ) -> SResult<Element> {
-        let s = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|v| v.as_millis())
-            .unwrap_or(0);
-
-        loop {
-            let n = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|v| v.as_millis())
-                .unwrap_or(0);
-            if n - s > timeout {
-                return Err(SError::Timeout("wait timeout".to_string()));
-            }
-
-            match self.find_element(by.as_ref()) {
-                Ok(ele) => {
-                    if on(&ele).is_ok_and(|f| f) {
-                        return Ok(ele);
-                    }
-                }
-                Err(e) => {
-                    let n = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|v| v.as_millis())
-                        .unwrap_or(0);
-                    if n - s > timeout {
-                        return Err(SError::Timeout(format!("{:?}", e)));
-                    }
-                }
-            }
-        }
+        self.wait_millis(timeout).until(|driver| {
+            Ok(driver
+                .find_element(by)
+                .ok()
+                .filter(|ele| on(ele).is_ok_and(|f| f)))
+        })
     }
 
     /// # Params
@@ -675,38 +1212,134 @@ impl Driver {
         self.wait_until_element_on(by, timeout, |ele| ele.is_displayed())
     }
 
-    pub fn wait_until_elements(&self, by: By<'_>, timeout: u128) -> SResult<Vec<Element>> {
-        let s = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|v| v.as_millis())
-            .unwrap_or(0);
-
-        let mut count = 0;
-        loop {
-            count += 1;
-            let n = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|v| v.as_millis())
-                .unwrap_or(0);
-            if n - s > timeout {
-                return Err(SError::Timeout("wait timeout".to_string()));
+    fn observe(&self, mut condition: serde_json::Value) -> SResult<Option<Element>> {
+        // 超时后页面中的 observer 自己断开
+        if let Some(ms) = self.session.script_timeout.get() {
+            condition["timeout"] = ms.into();
+        }
+        let v: Option<HashMap<String, String>> = match self.http.execute_async_script_with(
+            &self.session.session_id,
+            OBSERVE_SCRIPT,
+            vec![condition],
+        ) {
+            Err(e) if e.kind() == ErrorKind::ScriptTimeout => {
+                return Err(SError::Timeout(format!("{e}")))
             }
+            v => v?,
+        };
+        Ok(v.and_then(|f| f.into_iter().next()).map(|f| Element {
+            http: Rc::clone(&self.http),
+            session: Rc::clone(&self.session),
+            identify: f.0,
+            id: f.1,
+        }))
+    }
 
-            match self.find_elements(by.as_ref()) {
-                Ok(ele) => {
-                    return Ok(ele);
-                }
-                Err(e) => {
-                    let n = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|v| v.as_millis())
-                        .unwrap_or(0);
-                    if n - s > timeout {
-                        return Err(SError::Timeout(format!("{:?}", e)));
+    ///
+    /// 通过 MutationObserver 等待css选择器匹配到元素，不需要轮询，出现时间很短的元素也能捕获到
+    ///
+    /// 超时时间为 [TimeoutType::Script]
+    pub fn wait_for_selector(&self, css: &str) -> SResult<Element> {
+        self.observe(serde_json::json!({"kind": "attached", "selector": css}))?
+            .ok_or_else(SError::no_such_element)
+    }
+
+    ///
+    /// 通过 MutationObserver 等待css选择器匹配不到任何元素
+    ///
+    /// 超时时间为 [TimeoutType::Script]
+    pub fn wait_for_selector_detached(&self, css: &str) -> SResult<()> {
+        self.observe(serde_json::json!({"kind": "detached", "selector": css}))
+            .map(|_| ())
+    }
+
+    ///
+    /// 通过 MutationObserver 等待元素的文本发生变化
+    ///
+    /// 超时时间为 [TimeoutType::Script]
+    pub fn wait_for_text_change(&self, element: &Element) -> SResult<Element> {
+        self.observe(serde_json::json!({"kind": "text", "element": element.origin()}))?
+            .ok_or_else(SError::no_such_element)
+    }
+
+    ///
+    /// 通过 MutationObserver 等待元素的属性发生变化
+    ///
+    /// 超时时间为 [TimeoutType::Script]
+    pub fn wait_for_attribute_change(&self, element: &Element, name: &str) -> SResult<Element> {
+        self.observe(serde_json::json!({
+            "kind": "attribute",
+            "element": element.origin(),
+            "name": name,
+        }))?
+        .ok_or_else(SError::no_such_element)
+    }
+
+    /// 等待 `document.readyState` 变为 `complete`
+    pub fn wait_for_document_ready(&self, timeout: Duration) -> SResult<()> {
+        self.wait()
+            .timeout(timeout)
+            .message("document not ready")
+            .until(|driver| {
+                let ready: bool =
+                    driver.execute_script("return document.readyState === 'complete';", &[])?;
+                Ok(ready.then_some(()))
+            })
+    }
+
+    ///
+    /// 等待网络空闲，即连续 `idle` 时间内没有进行中的 fetch 和 XMLHttpRequest 请求
+    ///
+    /// 第一次调用时才会注入统计脚本，之前已经发出的请求无法统计
+    pub fn wait_for_network_idle(&self, idle: Duration, timeout: Duration) -> SResult<()> {
+        #[derive(Deserialize)]
+        struct NetworkState {
+            inflight: u32,
+            /// 距离上一次请求开始或结束的毫秒数
+            idle: f64,
+        }
+        self.wait()
+            .timeout(timeout)
+            .polling(idle.min(DEFAULT_POLLING))
+            .message("network not idle")
+            .until(|driver| {
+                let state: NetworkState = driver.http.execute_script_with(
+                    &driver.session.session_id,
+                    NETWORK_SCRIPT,
+                    Vec::new(),
+                )?;
+                Ok((state.inflight == 0 && state.idle >= idle.as_millis() as f64).then_some(()))
+            })
+    }
+
+    /// 等待所有探针都返回 `true`
+    pub fn wait_for_quiescence(
+        &self,
+        probes: &[&dyn QuiescenceProbe],
+        timeout: Duration,
+    ) -> SResult<()> {
+        let mut pending = "";
+        self.wait()
+            .timeout(timeout)
+            .until(|driver| {
+                for probe in probes {
+                    let quiet: bool = driver.execute_script(probe.script(), &[])?;
+                    if !quiet {
+                        pending = probe.name();
+                        return Ok(None);
                     }
                 }
-            }
-        }
+                Ok(Some(()))
+            })
+            .map_err(|e| match e {
+                SError::Timeout(m) => SError::Timeout(format!("{m}, pending probe: {pending}")),
+                e => e,
+            })
+    }
+
+    pub fn wait_until_elements(&self, by: By<'_>, timeout: u128) -> SResult<Vec<Element>> {
+        self.wait_millis(timeout)
+            .until(|driver| Ok(driver.find_elements(by).ok()))
     }
 
     pub fn wait_until_elements_on<T: Fn(&[Element]) -> SResult<bool>>(
@@ -715,36 +1348,11 @@ impl Driver {
         timeout: u128,
         on: T,
     ) -> SResult<Vec<Element>> {
-        let s = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|v| v.as_millis())
-            .unwrap_or(0);
-
-        loop {
-            let n = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|v| v.as_millis())
-                .unwrap_or(0);
-            if n - s > timeout {
-                return Err(SError::Timeout("wait timeout".to_string()));
-            }
-
-            match self.find_elements(by.as_ref()) {
-                Ok(ele) => {
-                    if on(&ele).is_ok_and(|f| f) {
-                        return Ok(ele);
-                    }
-                }
-                Err(e) => {
-                    let n = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|v| v.as_millis())
-                        .unwrap_or(0);
-                    if n - s > timeout {
-                        return Err(SError::Timeout(format!("{:?}", e)));
-                    }
-                }
-            }
-        }
+        self.wait_millis(timeout).until(|driver| {
+            Ok(driver
+                .find_elements(by)
+                .ok()
+                .filter(|ele| on(ele).is_ok_and(|f| f)))
+        })
     }
 }
@@ -1,13 +1,20 @@
 use std::{
+    collections::{BTreeMap, HashMap},
     fmt::{Debug, Display},
+    hash::{Hash, Hasher},
     rc::Rc,
+    time::Duration,
 };
 
+use serde::{de::DeserializeOwned, ser::SerializeMap, Deserialize, Serialize};
+
 use crate::{
-    driver::{Rect, Session},
-    http::Http,
+    actionability::{self, Checks},
+    driver::{AsBy, Rect, Session},
+    http::{Http, ELEMENT_IDENTIFIER},
+    inspect::{self, Snapshot},
     shadow::Shadow,
-    By, Origin, SResult,
+    By, Origin, SError, SResult,
 };
 
 pub struct Element {
@@ -24,6 +31,38 @@ impl Debug for Element {
             .finish()
     }
 }
+/// 共享同一个会话
+impl Clone for Element {
+    fn clone(&self) -> Self {
+        Element {
+            http: Rc::clone(&self.http),
+            session: Rc::clone(&self.session),
+            identify: self.identify.clone(),
+            id: self.id.clone(),
+        }
+    }
+}
+/// 同一个会话中引用相同的元素时相等，同一个元素多次查找得到的引用相同，不区分引用的 key
+impl PartialEq for Element {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.session.session_id == other.session.session_id
+    }
+}
+impl Eq for Element {}
+impl Hash for Element {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.session.session_id.hash(state);
+        self.id.hash(state);
+    }
+}
+/// 序列化为 webdriver 的元素引用 `{"element-6066-11e4-a52e-4f735466cecf": id}`，可以作为脚本参数
+impl Serialize for Element {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_map(Some(1))?;
+        s.serialize_entry(ELEMENT_IDENTIFIER, &self.id)?;
+        s.end()
+    }
+}
 impl Display for Element {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("identify=")?;
@@ -35,10 +74,15 @@ impl Display for Element {
 }
 
 impl Element {
-    pub fn find_element(&self, by: By<'_>) -> SResult<Element> {
-        let v = self
-            .http
-            .find_element_from_element(&self.session.session_id, &self.id, by)?;
+    /// 元素引用，可以作为脚本参数
+    pub(crate) fn origin(&self) -> Origin {
+        Origin::Element(self.identify.clone(), self.id.clone())
+    }
+
+    pub fn find_element<T: AsBy>(&self, by: T) -> SResult<Element> {
+        let v =
+            self.http
+                .find_element_from_element(&self.session.session_id, &self.id, by.as_by())?;
         Ok(Element {
             http: Rc::clone(&self.http),
             session: Rc::clone(&self.session),
@@ -47,10 +91,10 @@ impl Element {
         })
     }
 
-    pub fn find_elements(&self, by: By<'_>) -> SResult<Vec<Element>> {
-        let v = self
-            .http
-            .find_elements_from_element(&self.session.session_id, &self.id, by)?;
+    pub fn find_elements<T: AsBy>(&self, by: T) -> SResult<Vec<Element>> {
+        let v =
+            self.http
+                .find_elements_from_element(&self.session.session_id, &self.id, by.as_by())?;
         Ok(v.iter()
             .map(|f| Element {
                 http: Rc::clone(&self.http),
@@ -78,14 +122,63 @@ impl Element {
             .is_element_selected(&self.session.session_id, &self.id)
     }
 
+    /// 同 [Element::get_dom_attribute]
     pub fn get_attribute(&self, name: &str) -> SResult<Option<String>> {
+        self.get_dom_attribute(name)
+    }
+    ///
+    /// 同 [Element::get_dom_property]，可以是任意类型
+    ///
+    /// ```no_run
+    /// # use selenium::option::FirefoxBuilder;
+    /// # use selenium::driver::Driver;
+    /// # use selenium::By;
+    /// # let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+    /// let checkbox = driver.find_element(By::Id("agree")).unwrap();
+    /// let checked: Option<bool> = checkbox.get_property("checked").unwrap();
+    /// let value = checkbox.get_property::<String>("value").unwrap();
+    /// ```
+    pub fn get_property<T: DeserializeOwned>(&self, name: &str) -> SResult<Option<T>> {
+        self.get_dom_property(name)
+    }
+    ///
+    /// html 中写的属性值，不存在时为 `None`
+    ///
+    /// 对应 `/attribute` 接口，比如 `<input value="a">` 输入后依然是 `a`，`href` 是原始的相对地址
+    ///
+    /// 按协议 `checked`、`disabled` 等布尔属性存在时返回 `"true"`，而不是 html 中写的值
+    pub fn get_dom_attribute(&self, name: &str) -> SResult<Option<String>> {
         self.http
             .get_element_attribute(&self.session.session_id, &self.id, name)
     }
-    pub fn get_property(&self, name: &str) -> SResult<Option<String>> {
+    ///
+    /// js 对象上的属性，`undefined` 和 `null` 为 `None`
+    ///
+    /// 对应 `/property` 接口，比如 `value` 是当前输入的值，`href` 是完整的地址
+    pub fn get_dom_property<T: DeserializeOwned>(&self, name: &str) -> SResult<Option<T>> {
         self.http
             .get_element_property(&self.session.session_id, &self.id, name)
     }
+    /// 所有属性，按名称排序，值是 html 中写的原始值，布尔属性一般为空字符串
+    pub fn get_attributes(&self) -> SResult<BTreeMap<String, String>> {
+        self.call_script(
+            "var v = {}; for (var a of arguments[0].attributes) { v[a.name] = a.value; } return v;",
+            vec![],
+        )
+    }
+    pub fn has_attribute(&self, name: &str) -> SResult<bool> {
+        self.call_script(
+            "return arguments[0].hasAttribute(arguments[1]);",
+            vec![name.into()],
+        )
+    }
+    pub fn get_class_list(&self) -> SResult<Vec<String>> {
+        self.call_script("return Array.from(arguments[0].classList);", vec![])
+    }
+    /// `data-*` 属性，名称同 `dataset`，比如 `data-test-id` 为 `testId`
+    pub fn get_data_attributes(&self) -> SResult<BTreeMap<String, String>> {
+        self.call_script("return Object.assign({}, arguments[0].dataset);", vec![])
+    }
     pub fn get_css_value(&self, name: &str) -> SResult<String> {
         self.http
             .get_element_css_value(&self.session.session_id, &self.id, name)
@@ -109,15 +202,25 @@ impl Element {
     }
     /// 左键点击元素
     pub fn click(&self) -> SResult<()> {
-        self.http.element_click(&self.session.session_id, &self.id)
+        self.when_actionable(Checks::CLICK, || {
+            self.http.element_click(&self.session.session_id, &self.id)
+        })
     }
     pub fn clear(&self) -> SResult<()> {
-        self.http.element_clear(&self.session.session_id, &self.id)
+        self.when_actionable(Checks::INPUT, || {
+            self.http.element_clear(&self.session.session_id, &self.id)
+        })
     }
     /// 发送key，可以当做键盘输入
     pub fn send_keys(&self, key: &str) -> SResult<()> {
-        self.http
-            .element_send_keys(&self.session.session_id, &self.id, key)
+        self.when_actionable(Checks::INPUT, || {
+            self.http
+                .element_send_keys(&self.session.session_id, &self.id, key)
+        })
+    }
+
+    fn when_actionable(&self, checks: Checks, action: impl FnMut() -> SResult<()>) -> SResult<()> {
+        actionability::run(&self.http, &self.session, &[&self.origin()], checks, action)
     }
 
     pub fn take_screenshot(&self) -> SResult<Vec<u8>> {
@@ -126,9 +229,598 @@ impl Element {
     }
 
     pub fn is_displayed(&self) -> SResult<bool> {
-        self.http.is_element_displayed(
+        self.http
+            .is_element_displayed(&self.session.session_id, self.origin())
+    }
+}
+
+// 节点关系
+impl Element {
+    /// 父元素，`html` 元素和 shadow root 中的顶层元素没有父元素
+    pub fn parent(&self) -> SResult<Option<Element>> {
+        self.first_of(
+            "var p = arguments[0].parentElement; return p ? [p] : [];",
+            vec![],
+        )
+    }
+
+    pub fn children(&self) -> SResult<Vec<Element>> {
+        self.call_script_elements("return Array.from(arguments[0].children);", vec![])
+    }
+
+    pub fn next_sibling(&self) -> SResult<Option<Element>> {
+        self.first_of(
+            "var e = arguments[0].nextElementSibling; return e ? [e] : [];",
+            vec![],
+        )
+    }
+
+    pub fn previous_sibling(&self) -> SResult<Option<Element>> {
+        self.first_of(
+            "var e = arguments[0].previousElementSibling; return e ? [e] : [];",
+            vec![],
+        )
+    }
+
+    /// 匹配 `css` 的最近的祖先元素，同 dom 的 `closest`，包括自身
+    pub fn closest(&self, css: &str) -> SResult<Option<Element>> {
+        self.first_of(
+            "var e = arguments[0].closest(arguments[1]); return e ? [e] : [];",
+            vec![css.into()],
+        )
+    }
+
+    /// 所有祖先元素，从父元素到 `html`
+    pub fn ancestors(&self) -> SResult<Vec<Element>> {
+        self.call_script_elements(
+            "var v = []; for (var e = arguments[0].parentElement; e; e = e.parentElement) { v.push(e); } return v;",
+            vec![],
+        )
+    }
+
+    /// 在父元素的子元素中的位置，从0开始
+    pub fn index_in_parent(&self) -> SResult<usize> {
+        self.call_script(
+            "var e = arguments[0]; var p = e.parentElement || e.parentNode; return p ? Array.prototype.indexOf.call(p.children, e) : 0;",
+            vec![],
+        )
+    }
+
+    fn first_of(&self, script: &str, args: Vec<serde_json::Value>) -> SResult<Option<Element>> {
+        Ok(self.call_script_elements(script, args)?.into_iter().next())
+    }
+}
+
+// 可见性
+impl Element {
+    ///
+    /// 可见部分占元素面积的比例是否达到 `threshold`，`threshold` 为0时只要有一部分在视口中即可
+    ///
+    /// 只考虑视口和 overflow 的裁剪，不考虑遮挡，遮挡参考 [Element::is_obscured]
+    pub fn is_in_viewport(&self, threshold: f64) -> SResult<ViewportVisibility> {
+        let mut v: ViewportVisibility = self.call_script(VIEWPORT_SCRIPT, vec![])?;
+        v.in_viewport = if threshold <= 0.0 {
+            v.ratio > 0.0
+        } else {
+            v.ratio >= threshold
+        };
+        Ok(v)
+    }
+
+    /// 元素在视口中可见部分占元素面积的比例，0到1
+    pub fn visible_ratio(&self) -> SResult<f64> {
+        Ok(self.is_in_viewport(0.0)?.ratio)
+    }
+
+    ///
+    /// 检查元素的中心和四个角是否被其他元素遮挡，比如固定在顶部的导航栏
+    ///
+    /// 没有被遮挡时返回 `None`，在视口外的点不检查
+    pub fn is_obscured(&self) -> SResult<Option<Obscured>> {
+        #[derive(Deserialize)]
+        struct Raw {
+            element: Vec<HashMap<String, String>>,
+            points: Vec<HitPoint>,
+        }
+        let raw: Raw = self.call_script(OBSCURED_SCRIPT, vec![])?;
+        Ok(raw
+            .element
+            .into_iter()
+            .filter_map(|f| f.into_iter().next())
+            .next()
+            .map(|(identify, id)| Obscured {
+                by: Element {
+                    http: Rc::clone(&self.http),
+                    session: Rc::clone(&self.session),
+                    identify,
+                    id,
+                },
+                points: raw.points,
+            }))
+    }
+}
+
+const VIEWPORT_SCRIPT: &str = include_str!("js/viewport.js");
+const OBSCURED_SCRIPT: &str = include_str!("js/obscured.js");
+
+/// [Element::is_in_viewport] 的结果
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ViewportVisibility {
+    /// 是否达到了指定的比例
+    #[serde(default)]
+    pub in_viewport: bool,
+    /// 可见部分占元素面积的比例，0到1
+    pub ratio: f64,
+    /// 元素相对于视口的位置
+    pub rect: DomRect,
+    pub viewport: Size,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Size {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// [Element::is_obscured] 的结果
+#[derive(Debug, Clone)]
+pub struct Obscured {
+    /// 遮挡元素，多个点被不同元素遮挡时为第一个
+    pub by: Element,
+    /// 被遮挡的点
+    pub points: Vec<HitPoint>,
+}
+
+/// 检查遮挡时使用的点
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitPoint {
+    Center,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+// dom
+impl Element {
+    pub fn inner_html(&self) -> SResult<String> {
+        self.call_script("return arguments[0].innerHTML;", vec![])
+    }
+
+    pub fn outer_html(&self) -> SResult<String> {
+        self.call_script("return arguments[0].outerHTML;", vec![])
+    }
+
+    pub fn set_inner_html(&self, html: &str) -> SResult<()> {
+        self.call_script("arguments[0].innerHTML = arguments[1];", vec![html.into()])
+    }
+
+    ///
+    /// 直接修改 value 并触发 `input` 和 `change` 事件
+    ///
+    /// 不会模拟键盘输入，适合填写大量文字或者 `send_keys` 无法输入的控件
+    pub fn set_value_js(&self, value: &str) -> SResult<()> {
+        self.call_script(SET_VALUE_SCRIPT, vec![value.into()])
+    }
+
+    pub fn scroll_into_view(&self, options: ScrollOptions) -> SResult<()> {
+        self.call_script(
+            "arguments[0].scrollIntoView(arguments[1]);",
+            vec![serde_json::to_value(options)?],
+        )
+    }
+
+    pub fn focus(&self) -> SResult<()> {
+        self.call_script("arguments[0].focus();", vec![])
+    }
+
+    pub fn blur(&self) -> SResult<()> {
+        self.call_script("arguments[0].blur();", vec![])
+    }
+
+    ///
+    /// 触发事件，`init` 为事件的初始化参数，默认冒泡并且可以取消，`()` 表示使用默认值
+    ///
+    /// 返回值同 `dispatchEvent`，事件被 `preventDefault` 时为 `false`
+    ///
+    /// ```no_run
+    /// # use selenium::option::FirefoxBuilder;
+    /// # use selenium::driver::Driver;
+    /// # use selenium::By;
+    /// # let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+    /// let element = driver.find_element(By::Id("name")).unwrap();
+    /// element.dispatch_event("mouseenter", ()).unwrap();
+    /// element
+    ///     .dispatch_event("custom", serde_json::json!({"detail": {"id": 1}}))
+    ///     .unwrap();
+    /// ```
+    pub fn dispatch_event<T: Serialize>(&self, event: &str, init: T) -> SResult<bool> {
+        self.call_script(
+            DISPATCH_EVENT_SCRIPT,
+            vec![event.into(), serde_json::to_value(init)?],
+        )
+    }
+
+    /// 用红色边框标出元素，`duration` 之后恢复，调试时使用，不会阻塞
+    pub fn highlight(&self, duration: Duration) -> SResult<()> {
+        self.call_script(
+            HIGHLIGHT_SCRIPT,
+            vec![(duration.as_millis().min(u64::MAX as u128) as u64).into()],
+        )
+    }
+
+    /// 相对于视口的位置，[Element::get_rect] 是相对于文档的位置
+    pub fn get_bounding_client_rect(&self) -> SResult<DomRect> {
+        self.call_script(
+            "return arguments[0].getBoundingClientRect().toJSON();",
+            vec![],
+        )
+    }
+
+    /// 脚本返回元素数组，转换为 [Element]
+    fn call_script_elements(
+        &self,
+        script: &str,
+        args: Vec<serde_json::Value>,
+    ) -> SResult<Vec<Element>> {
+        let v: Vec<HashMap<String, String>> = self.call_script(script, args)?;
+        Ok(v.into_iter()
+            .filter_map(|f| f.into_iter().next())
+            .map(|(identify, id)| Element {
+                http: Rc::clone(&self.http),
+                session: Rc::clone(&self.session),
+                identify,
+                id,
+            })
+            .collect())
+    }
+
+    /// 执行脚本，`arguments[0]` 为当前元素，其余参数依次排在后面
+    fn call_script<T: DeserializeOwned>(
+        &self,
+        script: &str,
+        args: Vec<serde_json::Value>,
+    ) -> SResult<T> {
+        let mut v = vec![serde_json::to_value(self.origin())?];
+        v.extend(args);
+        self.http
+            .execute_script_with(&self.session.session_id, script, v)
+    }
+}
+
+/// 通过原型上的 setter 赋值，react 等框架才能收到变化
+const SET_VALUE_SCRIPT: &str = r#"var element = arguments[0];
+var proto = Object.getPrototypeOf(element);
+var descriptor = Object.getOwnPropertyDescriptor(proto, 'value');
+if (descriptor && descriptor.set) {
+  descriptor.set.call(element, arguments[1]);
+} else {
+  element.value = arguments[1];
+}
+element.dispatchEvent(new Event('input', { bubbles: true }));
+element.dispatchEvent(new Event('change', { bubbles: true }));"#;
+
+const DISPATCH_EVENT_SCRIPT: &str = r#"var init = Object.assign({ bubbles: true, cancelable: true }, arguments[2]);
+return arguments[0].dispatchEvent(new CustomEvent(arguments[1], init));"#;
+
+/// 重复调用时只记录第一次的原始样式
+const HIGHLIGHT_SCRIPT: &str = r#"var element = arguments[0];
+if (element.__seleniumOutline === undefined) {
+  element.__seleniumOutline = element.style.outline;
+}
+element.style.outline = '2px solid red';
+clearTimeout(element.__seleniumHighlight);
+element.__seleniumHighlight = setTimeout(function () {
+  element.style.outline = element.__seleniumOutline;
+  delete element.__seleniumOutline;
+}, arguments[1]);"#;
+
+/// 滚动的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollBehavior {
+    #[default]
+    Auto,
+    Smooth,
+    Instant,
+}
+
+/// 滚动后元素在视口中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollPosition {
+    Start,
+    Center,
+    End,
+    Nearest,
+}
+
+///
+/// [Element::scroll_into_view] 的参数，同 `scrollIntoView` 的 options
+///
+/// 默认值和浏览器一致，纵向对齐到顶部，横向滚动最少的距离
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ScrollOptions {
+    pub behavior: ScrollBehavior,
+    pub block: ScrollPosition,
+    pub inline: ScrollPosition,
+}
+
+impl Default for ScrollOptions {
+    fn default() -> Self {
+        ScrollOptions {
+            behavior: ScrollBehavior::Auto,
+            block: ScrollPosition::Start,
+            inline: ScrollPosition::Nearest,
+        }
+    }
+}
+
+impl ScrollOptions {
+    /// 滚动到视口中间
+    pub fn center() -> Self {
+        ScrollOptions {
+            block: ScrollPosition::Center,
+            inline: ScrollPosition::Center,
+            ..Default::default()
+        }
+    }
+
+    pub fn behavior(mut self, behavior: ScrollBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+
+    pub fn block(mut self, block: ScrollPosition) -> Self {
+        self.block = block;
+        self
+    }
+
+    pub fn inline(mut self, inline: ScrollPosition) -> Self {
+        self.inline = inline;
+        self
+    }
+}
+
+/// `getBoundingClientRect` 的结果
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct DomRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+const DRAG_DROP_SCRIPT: &str = include_str!("js/drag_drop.js");
+
+// html5 拖放
+impl Element {
+    ///
+    /// 用脚本模拟 html5 拖放，把当前元素拖到 `target` 上
+    ///
+    /// 依次触发 `dragstart`、`drag`、`dragenter`、`dragover`、`drop`、`dragend`，共用同一个 `DataTransfer`。
+    /// 浏览器的 actions 不会触发这些事件，`draggable` 元素需要使用这个方法，
+    /// 只监听鼠标事件的拖动参考 [Action::drag_and_drop](crate::actions::Action::drag_and_drop)
+    ///
+    /// 返回 `target` 是否接受了放置，和浏览器一样，`dragover` 没有被取消时不会触发 `drop`
+    ///
+    /// ```no_run
+    /// # use selenium::option::FirefoxBuilder;
+    /// # use selenium::driver::Driver;
+    /// # use selenium::By;
+    /// # let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+    /// let card = driver.find_element(By::Css(".card")).unwrap();
+    /// let column = driver.find_element(By::Css(".column.done")).unwrap();
+    /// assert!(card.html5_drag_to(&column).unwrap());
+    /// ```
+    pub fn html5_drag_to(&self, target: &Element) -> SResult<bool> {
+        self.call_script(
+            DRAG_DROP_SCRIPT,
+            vec![serde_json::to_value(target.origin())?],
+        )
+    }
+
+    ///
+    /// 模拟从页面外拖入文件，放到当前元素上
+    ///
+    /// 触发 `dragenter`、`dragover`、`drop`，`dataTransfer.files` 为 `files`，返回是否接受了放置
+    ///
+    /// ```no_run
+    /// # use selenium::option::FirefoxBuilder;
+    /// # use selenium::driver::Driver;
+    /// # use selenium::element::DropFile;
+    /// # use selenium::By;
+    /// # let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+    /// let zone = driver.find_element(By::Id("upload")).unwrap();
+    /// zone.drop_files(&[
+    ///     DropFile::new("a.txt", "hello").mime("text/plain"),
+    ///     DropFile::from_path("tests/avatar.png").unwrap(),
+    /// ])
+    /// .unwrap();
+    /// ```
+    pub fn drop_files(&self, files: &[DropFile]) -> SResult<bool> {
+        self.http.execute_script_with(
             &self.session.session_id,
-            Origin::Element(self.identify.clone(), self.id.clone()),
+            DRAG_DROP_SCRIPT,
+            vec![
+                serde_json::Value::Null,
+                serde_json::to_value(self.origin())?,
+                serde_json::to_value(files)?,
+            ],
         )
     }
 }
+
+/// [Element::drop_files] 拖入的文件，内容按 base64 编码后随脚本一起发送，不适合很大的文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DropFile {
+    pub name: String,
+    /// 文件类型，比如 `image/png`，默认为空，同浏览器无法识别的文件
+    pub mime: String,
+    #[serde(serialize_with = "serialize_base64")]
+    pub content: Vec<u8>,
+}
+
+fn serialize_base64<S>(v: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&crate::base64::encode(v))
+}
+
+impl DropFile {
+    pub fn new(name: &str, content: impl Into<Vec<u8>>) -> Self {
+        DropFile {
+            name: name.to_string(),
+            mime: String::new(),
+            content: content.into(),
+        }
+    }
+
+    /// 读取本地文件，文件名为路径的最后一部分
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> SResult<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(DropFile::new(&name, std::fs::read(path)?))
+    }
+
+    pub fn mime(mut self, mime: &str) -> Self {
+        self.mime = mime.to_string();
+        self
+    }
+}
+
+impl Element {
+    /// 一次脚本调用读取标签名、文字、位置、状态和所有属性
+    pub fn snapshot(&self) -> SResult<Snapshot> {
+        inspect::inspect(std::slice::from_ref(self), &Snapshot::FIELDS)?
+            .pop()
+            .ok_or_else(SError::no_such_element)
+    }
+}
+
+impl Element {
+    /// 能唯一定位到该元素的最短 css 选择器，优先使用 id、data-testid、name、aria-label，最后才使用 `:nth-child`
+    pub fn unique_selector(&self) -> SResult<UniqueSelector> {
+        self.build_selector(false)
+    }
+
+    /// 同 [Element::unique_selector]，生成 xpath，shadow root 中的元素会报错
+    pub fn xpath(&self) -> SResult<UniqueSelector> {
+        self.build_selector(true)
+    }
+
+    fn build_selector(&self, xpath: bool) -> SResult<UniqueSelector> {
+        #[derive(Deserialize)]
+        struct Raw {
+            selector: String,
+            specificity: Specificity,
+        }
+        let raw: Raw = self.http.execute_script_with(
+            &self.session.session_id,
+            UNIQUE_SELECTOR_SCRIPT,
+            vec![
+                serde_json::to_value(self.origin())?,
+                serde_json::Value::from(if xpath { "xpath" } else { "css" }),
+            ],
+        )?;
+        Ok(UniqueSelector {
+            selector: raw.selector,
+            specificity: raw.specificity,
+            xpath,
+        })
+    }
+}
+
+const UNIQUE_SELECTOR_SCRIPT: &str = include_str!("js/unique_selector.js");
+
+/// [Element::unique_selector] 和 [Element::xpath] 的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniqueSelector {
+    pub selector: String,
+    /// 选择器中最不稳定的部分
+    pub specificity: Specificity,
+    xpath: bool,
+}
+
+impl UniqueSelector {
+    pub fn as_by(&self) -> By<'_> {
+        if self.xpath {
+            By::XPath(&self.selector)
+        } else {
+            By::Css(&self.selector)
+        }
+    }
+}
+
+impl AsBy for UniqueSelector {
+    fn as_by(&self) -> By<'_> {
+        UniqueSelector::as_by(self)
+    }
+}
+
+impl Display for UniqueSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.selector)
+    }
+}
+
+/// 选择器依赖的定位信息，从稳定到不稳定排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Specificity {
+    Id,
+    /// `data-testid`、`data-cy` 等测试专用的属性
+    TestId,
+    /// `name`、`aria-label`
+    Attribute,
+    Class,
+    Tag,
+    /// 用到了 `:nth-child` 或者 xpath 的下标，页面结构变化后很可能失效
+    Positional,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, collections::HashSet, rc::Rc};
+
+    use crate::{driver::Session, http::Http};
+
+    use super::Element;
+
+    #[test]
+    fn test_identity() {
+        let http = Rc::new(Http::new("http://localhost:4444", 1));
+        let session = Rc::new(Session {
+            session_id: "test".to_string(),
+            auto_wait: Cell::new(None),
+            script_timeout: Cell::new(None),
+        });
+        let element = |identify: &str| Element {
+            http: Rc::clone(&http),
+            session: Rc::clone(&session),
+            identify: identify.to_string(),
+            id: "e1".to_string(),
+        };
+        let w3c = element("element-6066-11e4-a52e-4f735466cecf");
+        let legacy = element("ELEMENT");
+        assert_eq!(w3c, legacy);
+        assert_eq!(1, HashSet::from([w3c.clone(), legacy.clone()]).len());
+        assert_eq!(
+            serde_json::to_string(&w3c).unwrap(),
+            serde_json::to_string(&legacy).unwrap()
+        );
+        assert_eq!(
+            r#"{"element-6066-11e4-a52e-4f735466cecf":"e1"}"#,
+            serde_json::to_string(&legacy).unwrap()
+        );
+    }
+}
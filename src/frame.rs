@@ -0,0 +1,208 @@
+//!
+//! 嵌套 frame 的路径
+//!
+//! ```no_run
+//! use selenium::option::FirefoxBuilder;
+//! use selenium::driver::Driver;
+//! use selenium::frame::FramePath;
+//! use selenium::By;
+//! let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+//! let path = FramePath::new().then(By::Id("app")).then(By::Css("iframe.editor"));
+//! let text = driver
+//!     .within_frames(&path, |d| d.find_element(By::Css("body"))?.get_text())
+//!     .unwrap();
+//! // 在所有 frame 中查找，找到后停留在元素所在的 frame
+//! let (element, path) = driver.find_in_all_frames(By::Id("save")).unwrap();
+//! element.click().unwrap();
+//! ```
+use std::fmt::Display;
+
+use crate::{
+    driver::{AsBy, ByBuf, Session, SwitchToFrame},
+    http::Http,
+    By, ErrorKind, SError, SResult,
+};
+
+/// 查找 frame 元素的选择器
+pub(crate) const FRAME_SELECTOR: &str = "iframe, frame";
+
+/// 路径中的一层 frame
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FrameRef {
+    /// 当前文档中第几个 `iframe` 或 `frame` 元素，按文档顺序从0开始
+    Index(usize),
+    /// frame 元素的查找方式
+    By(ByBuf),
+}
+
+impl Display for FrameRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameRef::Index(i) => write!(f, "{i}"),
+            FrameRef::By(by) => write!(f, "{by}"),
+        }
+    }
+}
+
+/// 从顶层文档开始依次进入的 frame，空路径表示顶层文档
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FramePath(Vec<FrameRef>);
+
+impl FramePath {
+    pub fn new() -> Self {
+        FramePath(Vec::new())
+    }
+
+    /// 再进入一层由 `by` 找到的 frame
+    pub fn then<T: AsBy>(mut self, by: T) -> Self {
+        self.0.push(FrameRef::By(by.as_by().into()));
+        self
+    }
+
+    /// 再进入一层第 `index` 个 frame
+    pub fn then_index(mut self, index: usize) -> Self {
+        self.0.push(FrameRef::Index(index));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, FrameRef> {
+        self.0.iter()
+    }
+
+    pub(crate) fn push(&mut self, frame: FrameRef) {
+        self.0.push(frame);
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<FrameRef> {
+        self.0.pop()
+    }
+}
+
+impl Display for FramePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("top")?;
+        for frame in &self.0 {
+            write!(f, " > {frame}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<ByBuf>> for FramePath {
+    fn from(value: Vec<ByBuf>) -> Self {
+        FramePath(value.into_iter().map(FrameRef::By).collect())
+    }
+}
+
+/// 回到顶层文档后依次进入 `path` 中的 frame
+pub(crate) fn enter(http: &Http, session: &Session, path: &FramePath) -> SResult<()> {
+    http.switch_to_frame(&session.session_id, SwitchToFrame::Null)?;
+    for frame in path.iter() {
+        enter_one(http, session, frame)?;
+    }
+    Ok(())
+}
+
+/// 从当前文档进入一层 frame
+pub(crate) fn enter_one(http: &Http, session: &Session, frame: &FrameRef) -> SResult<()> {
+    let (_, id) = match frame {
+        FrameRef::By(by) => http.find_element(&session.session_id, &by.as_by())?,
+        FrameRef::Index(i) => http
+            .find_elements(&session.session_id, &By::Css(FRAME_SELECTOR))?
+            .into_iter()
+            .nth(*i)
+            .ok_or_else(|| {
+                SError::NotFound(ErrorKind::NoSuchFrame, format!("frame {i} not found"))
+            })?,
+    };
+    http.switch_to_frame(&session.session_id, SwitchToFrame::Element(id))
+}
+
+///
+/// 进入 `path` 执行 `f`，结束后无论成功与否都回到顶层文档
+///
+/// 路径为空时不切换
+pub(crate) fn within<T>(
+    http: &Http,
+    session: &Session,
+    path: &FramePath,
+    f: impl FnOnce() -> SResult<T>,
+) -> SResult<T> {
+    if path.is_empty() {
+        return f();
+    }
+    let result = enter(http, session, path).and_then(|_| f());
+    let back = http.switch_to_frame(&session.session_id, SwitchToFrame::Null);
+    let v = result?;
+    back?;
+    Ok(v)
+}
+
+/// 当前所在的 frame 在每一层 `window.frames` 中的序号，跨域的 frame 也可以比较
+const CURRENT_SCRIPT: &str = "var path = []; var w = window; while (w !== w.parent) { var p = w.parent; for (var i = 0; i < p.frames.length; i++) { if (p.frames[i] === w) { path.unshift(i); break; } } w = p; } return path;";
+
+/// 当前所在的 frame，顶层文档为空
+pub(crate) fn current(http: &Http, session: &Session) -> SResult<Vec<usize>> {
+    http.execute_script_with(&session.session_id, CURRENT_SCRIPT, Vec::new())
+}
+
+/// 回到 [current] 得到的 frame
+pub(crate) fn restore(http: &Http, session: &Session, path: &[usize]) -> SResult<()> {
+    http.switch_to_frame(&session.session_id, SwitchToFrame::Null)?;
+    for i in path {
+        http.switch_to_frame(&session.session_id, SwitchToFrame::Number(*i))?;
+    }
+    Ok(())
+}
+
+///
+/// 同 [within]，结束后回到调用前所在的 frame
+pub(crate) fn within_current<T>(
+    http: &Http,
+    session: &Session,
+    path: &FramePath,
+    f: impl FnOnce() -> SResult<T>,
+) -> SResult<T> {
+    if path.is_empty() {
+        return f();
+    }
+    let previous = current(http, session)?;
+    let result = enter(http, session, path).and_then(|_| f());
+    let back = restore(http, session, &previous);
+    let v = result?;
+    back?;
+    Ok(v)
+}
+
+/// 查找时可以忽略，继续在其他 frame 中查找的错误
+pub(crate) fn is_skippable(kind: ErrorKind) -> bool {
+    matches!(
+        kind,
+        ErrorKind::NoSuchElement | ErrorKind::NoSuchFrame | ErrorKind::StaleElementReference
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{driver::ByBuf, By};
+
+    use super::FramePath;
+
+    #[test]
+    fn display() {
+        let path = FramePath::new().then(By::Id("app")).then_index(1);
+        assert_eq!("top > id=app > 1", path.to_string());
+        assert_eq!(
+            FramePath::new().then(By::Css("iframe")),
+            FramePath::from(vec![ByBuf::Css("iframe".to_string())])
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//!
+//! 触摸手势
+//!
+//! 手势由一根或多根手指 [PointerSource] 组成，`center` 一般是元素，坐标相对于元素的中心
+//!
+//! ```no_run
+//! use selenium::option::FirefoxBuilder;
+//! use selenium::driver::Driver;
+//! use selenium::By;
+//! let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+//! let map = driver.find_element(By::Id("map")).unwrap();
+//! driver.actions().pinch(&map, 200, 50, 500).perform().unwrap();
+//! driver.actions().swipe(&map, 0, -300, 300).perform().unwrap();
+//! ```
+use crate::actions::{Action, Origin, PointerSource};
+
+/// 旋转时分成多少段移动
+const ROTATE_STEPS: u32 = 12;
+
+fn finger(index: usize) -> PointerSource {
+    PointerSource::touch(&format!("finger{index}"))
+}
+
+/// 单击
+pub fn tap(center: impl Into<Origin>) -> PointerSource {
+    finger(0).move_to(center.into(), 0, 0, 0).down().up()
+}
+
+/// 按住 `duration` 毫秒后松开
+pub fn long_press(center: impl Into<Origin>, duration: u32) -> PointerSource {
+    finger(0)
+        .move_to(center.into(), 0, 0, 0)
+        .down()
+        .pause(duration)
+        .up()
+}
+
+/// 从 `center` 开始，在 `duration` 毫秒内滑动 `(dx, dy)`
+pub fn swipe(center: impl Into<Origin>, dx: i32, dy: i32, duration: u32) -> PointerSource {
+    finger(0)
+        .move_to(center.into(), 0, 0, 0)
+        .down()
+        .move_to(Origin::Pointer, dx, dy, duration)
+        .up()
+}
+
+///
+/// 两根手指水平放在 `center` 两侧，距离在 `duration` 毫秒内从 `from` 变为 `to`
+///
+/// `to` 小于 `from` 时为捏合缩小，大于时为张开放大
+pub fn pinch(center: impl Into<Origin>, from: i32, to: i32, duration: u32) -> [PointerSource; 2] {
+    let center = center.into();
+    [(0, -1), (1, 1)].map(|(index, side)| {
+        finger(index)
+            .move_to(center.clone(), side * from / 2, 0, 0)
+            .down()
+            .move_to(center.clone(), side * to / 2, 0, duration)
+            .up()
+    })
+}
+
+///
+/// 两根手指放在以 `center` 为圆心、半径为 `radius` 的圆上，在 `duration` 毫秒内旋转 `degrees` 度
+///
+/// 正数为顺时针
+pub fn rotate(
+    center: impl Into<Origin>,
+    radius: i32,
+    degrees: f64,
+    duration: u32,
+) -> [PointerSource; 2] {
+    let center = center.into();
+    [(0, 0.0), (1, 180.0)].map(|(index, start): (usize, f64)| {
+        let point = |angle: f64| {
+            let radian = angle.to_radians();
+            (
+                (radius as f64 * radian.cos()).round() as i32,
+                (radius as f64 * radian.sin()).round() as i32,
+            )
+        };
+        let (x, y) = point(start);
+        let mut source = finger(index).move_to(center.clone(), x, y, 0).down();
+        for step in 1..=ROTATE_STEPS {
+            let (x, y) = point(start + degrees * step as f64 / ROTATE_STEPS as f64);
+            source = source.move_to(center.clone(), x, y, duration / ROTATE_STEPS);
+        }
+        source.up()
+    })
+}
+
+// 手势
+impl Action {
+    pub fn tap(self, center: impl Into<Origin>) -> Self {
+        self.add_source(tap(center))
+    }
+
+    pub fn long_press(self, center: impl Into<Origin>, duration: u32) -> Self {
+        self.add_source(long_press(center, duration))
+    }
+
+    pub fn swipe(self, center: impl Into<Origin>, dx: i32, dy: i32, duration: u32) -> Self {
+        self.add_source(swipe(center, dx, dy, duration))
+    }
+
+    pub fn pinch(self, center: impl Into<Origin>, from: i32, to: i32, duration: u32) -> Self {
+        self.parallel(pinch(center, from, to, duration))
+    }
+
+    pub fn rotate(
+        self,
+        center: impl Into<Origin>,
+        radius: i32,
+        degrees: f64,
+        duration: u32,
+    ) -> Self {
+        self.parallel(rotate(center, radius, degrees, duration))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::actions::{Origin, PointerKind};
+
+    use super::{pinch, rotate};
+
+    #[test]
+    fn test_pinch() {
+        let [a, b] = pinch(Origin::Viewport, 200, 50, 300);
+        assert_eq!(PointerKind::Touch, a.kind());
+        assert_ne!(a.id(), b.id());
+        assert_eq!(
+            json!([
+                {"type": "pointerMove", "origin": "viewport", "duration": 0, "x": -100, "y": 0},
+                {"type": "pointerDown", "button": 0, "width": 0.0, "height": 0.0, "pressure": 0.0,
+                    "tangentialPressure": 0.0, "tiltX": 0, "tiltY": 0, "twist": 0,
+                    "altitudeAngle": 0.0, "azimuthAngle": 0.0},
+                {"type": "pointerMove", "origin": "viewport", "duration": 300, "x": -25, "y": 0},
+                {"type": "pointerUp", "button": 0},
+            ]),
+            serde_json::to_value(&a.actions).unwrap()
+        );
+        assert_eq!(Some(100), b.actions[0].x);
+        assert_eq!(Some(25), b.actions[2].x);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let [a, b] = rotate(Origin::Viewport, 100, 90.0, 1200);
+        // 移动、按下、12段旋转、松开
+        assert_eq!(15, a.actions.len());
+        assert_eq!((Some(100), Some(0)), (a.actions[0].x, a.actions[0].y));
+        assert_eq!((Some(0), Some(100)), (a.actions[13].x, a.actions[13].y));
+        assert_eq!((Some(-100), Some(0)), (b.actions[0].x, b.actions[0].y));
+        assert_eq!((Some(0), Some(-100)), (b.actions[13].x, b.actions[13].y));
+        assert_eq!(Some(100), a.actions[2].duration);
+    }
+}
@@ -1,6 +1,6 @@
 //!
 //! 负责实际的http通信
-use std::{collections::HashMap, fmt::Display, ops::Deref, time::Duration};
+use std::{cell::Cell, collections::HashMap, fmt::Display, ops::Deref, time::Duration};
 
 use serde::{
     ser::{SerializeSeq, SerializeStruct},
@@ -13,9 +13,27 @@ use crate::{
     base64,
     driver::{By, Rect, Session, SwitchToFrame, TimeoutType},
     option::BrowserOption,
-    Origin, SError, SResult,
+    ErrorKind, Origin, SError, SResult,
 };
 
+/// w3c 规定的元素引用的key
+pub(crate) const ELEMENT_IDENTIFIER: &str = "element-6066-11e4-a52e-4f735466cecf";
+pub(crate) const SHADOW_IDENTIFIER: &str = "shadow-6066-11e4-a52e-4f735466cecf";
+
+const TEXT_SCRIPT: &str = include_str!("js/text.js");
+
+/// 页面中缓存 isDisplayed 的全局变量，页面跳转后 window 会重新创建，需要重新注入
+const IS_DISPLAYED_CACHE: &str = "__selenium_is_displayed";
+/// 页面中没有缓存时脚本的返回值
+const IS_DISPLAYED_MISSING: &str = "__selenium_is_displayed_missing";
+
+/// 脚本查找时的起点
+enum Scope<'a> {
+    Document,
+    Element(&'a str),
+    Shadow(&'a str),
+}
+
 #[derive(Deserialize)]
 pub(crate) struct ResponseWrapper<T> {
     pub(crate) value: T,
@@ -34,6 +52,8 @@ pub(crate) struct Http {
     url: String,
     timeout: u64,
     inner: ureq::Agent,
+    /// driver 是否支持 `/displayed` 接口，`None` 表示还没有请求过
+    native_displayed: Cell<Option<bool>>,
 }
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -70,6 +90,7 @@ where
             Device::Pointer(pointer) => s.serialize_element(pointer)?,
             Device::Keyboard(keyboard) => s.serialize_element(keyboard)?,
             Device::Wheel(wheel) => s.serialize_element(wheel)?,
+            Device::Pause => s.serialize_element(&serde_json::json!({ "type": "pause" }))?,
         }
     }
     s.end()
@@ -94,43 +115,17 @@ impl Serialize for By<'_> {
     where
         S: serde::Serializer,
     {
+        let (using, value) = self.selector().ok_or_else(|| {
+            serde::ser::Error::custom(format!("{self} is not a native locator strategy"))
+        })?;
         let mut s = serializer.serialize_struct("By", 2)?;
-        match self {
-            Self::Css(v) => {
-                s.serialize_field("using", "css selector")?;
-                s.serialize_field("value", v)?;
-            }
-            Self::LinkText(v) => {
-                s.serialize_field("using", "link text")?;
-                s.serialize_field("value", v)?;
-            }
-            Self::ParitialLinkText(v) => {
-                s.serialize_field("using", "partial link text")?;
-                s.serialize_field("value", v)?;
-            }
-            Self::TagName(v) => {
-                s.serialize_field("using", "tag name")?;
-                s.serialize_field("value", v)?;
-            }
-            Self::XPath(v) => {
-                s.serialize_field("using", "xpath")?;
-                s.serialize_field("value", v)?;
-            }
-            Self::Id(v) => {
-                s.serialize_field("using", "css selector")?;
-                s.serialize_field("value", format!("#{v}").as_str())?;
-            }
-            Self::Class(v) => {
-                s.serialize_field("using", "css selector")?;
-                s.serialize_field("value", format!(".{v}").as_str())?;
-            }
-        }
-
+        s.serialize_field("using", using)?;
+        s.serialize_field("value", &value)?;
         s.end()
     }
 }
 
-mod script {
+pub(crate) mod script {
     include!(concat!(env!("OUT_DIR"), "/is_displayed.rs"));
 }
 
@@ -170,6 +165,7 @@ impl Http {
                     .timeout_global(Some(Duration::from_secs(timeout)))
                     .build(),
             ),
+            native_displayed: Cell::new(None),
         }
     }
 
@@ -189,8 +185,11 @@ impl Http {
             }
             Method::Delete(uri) => self.inner.delete(uri).call(),
         }?;
-        if v.status().as_u16() == 500 {
-            return Err(SError::Http(500, v.body_mut().read_to_string()?));
+        if !v.status().is_success() {
+            return Err(SError::Http(
+                v.status().as_u16().into(),
+                v.body_mut().read_to_string()?,
+            ));
         }
         Ok(())
     }
@@ -232,6 +231,8 @@ impl Http {
         ))?;
         return Ok(Session {
             session_id: session.value.session_id.clone(),
+            auto_wait: Cell::new(None),
+            script_timeout: session.value.script_timeout.clone(),
         });
     }
 
@@ -344,14 +345,16 @@ impl Http {
             match id {
                 SwitchToFrame::Null => r#"{"id":null}"#.to_string(),
                 SwitchToFrame::Number(s) => format!(r#"{{"id":{s}}}"#),
-                SwitchToFrame::Element(s) => format!(r#"{{"id":"{s}"}}"#),
+                SwitchToFrame::Element(s) => {
+                    format!(r#"{{"id":{{"{ELEMENT_IDENTIFIER}":"{s}"}}}}"#)
+                }
             },
         ))
     }
 
     pub(crate) fn switch_to_parent_frame(&self, session_id: &str) -> SResult<()> {
         self.req_without_res(Method::Post(
-            format!("{}/session/{}/frame", self.url, session_id),
+            format!("{}/session/{}/frame/parent", self.url, session_id),
             String::new(),
         ))
     }
@@ -399,6 +402,16 @@ impl Http {
     }
 
     pub(crate) fn find_element(&self, session_id: &str, by: &By<'_>) -> SResult<(String, String)> {
+        if let Some(list) = by.alternatives() {
+            return find_any_of(by, &list, |by| self.find_element(session_id, by));
+        }
+        if let Some(query) = by.script_query() {
+            return self
+                .find_elements_by_script(session_id, Scope::Document, query)?
+                .into_iter()
+                .next()
+                .ok_or_else(SError::no_such_element);
+        }
         let res: ResponseWrapper<HashMap<String, String>> = self.req(Method::Post(
             format!("{}/session/{}/element", self.url, session_id),
             serde_json::to_string(by)?,
@@ -406,7 +419,7 @@ impl Http {
         for ele in res.value {
             return Ok(ele);
         }
-        Err(SError::Browser("element not found".to_string()))
+        Err(SError::no_such_element())
     }
 
     pub(crate) fn find_elements(
@@ -414,6 +427,12 @@ impl Http {
         session_id: &str,
         by: &By<'_>,
     ) -> SResult<Vec<(String, String)>> {
+        if let Some(list) = by.alternatives() {
+            return find_all_any_of(by, &list, |by| self.find_elements(session_id, by));
+        }
+        if let Some(query) = by.script_query() {
+            return self.find_elements_by_script(session_id, Scope::Document, query);
+        }
         let res: ResponseWrapper<Vec<HashMap<String, String>>> = self.req(Method::Post(
             format!("{}/session/{}/elements", self.url, session_id),
             serde_json::to_string(by)?,
@@ -436,6 +455,18 @@ impl Http {
         element_id: &str,
         by: By<'_>,
     ) -> SResult<(String, String)> {
+        if let Some(list) = by.alternatives() {
+            return find_any_of(&by, &list, |by| {
+                self.find_element_from_element(session_id, element_id, *by)
+            });
+        }
+        if let Some(query) = by.script_query() {
+            return self
+                .find_elements_by_script(session_id, Scope::Element(element_id), query)?
+                .into_iter()
+                .next()
+                .ok_or_else(SError::no_such_element);
+        }
         let res: ResponseWrapper<HashMap<String, String>> = self.req(Method::Post(
             format!(
                 "{}/session/{}/element/{}/element",
@@ -446,7 +477,7 @@ impl Http {
         for ele in res.value {
             return Ok(ele);
         }
-        Err(SError::Browser("element not found".to_string()))
+        Err(SError::no_such_element())
     }
 
     pub(crate) fn find_elements_from_element(
@@ -455,6 +486,14 @@ impl Http {
         element_id: &str,
         by: By<'_>,
     ) -> SResult<Vec<(String, String)>> {
+        if let Some(list) = by.alternatives() {
+            return find_all_any_of(&by, &list, |by| {
+                self.find_elements_from_element(session_id, element_id, *by)
+            });
+        }
+        if let Some(query) = by.script_query() {
+            return self.find_elements_by_script(session_id, Scope::Element(element_id), query);
+        }
         let res: ResponseWrapper<Vec<HashMap<String, String>>> = self.req(Method::Post(
             format!(
                 "{}/session/{}/element/{}/elements",
@@ -474,6 +513,26 @@ impl Http {
             .collect())
     }
 
+    /// 协议不支持的查找方式，由注入的脚本查找
+    fn find_elements_by_script(
+        &self,
+        session_id: &str,
+        scope: Scope<'_>,
+        query: serde_json::Value,
+    ) -> SResult<Vec<(String, String)>> {
+        let scope = match scope {
+            Scope::Document => serde_json::Value::Null,
+            Scope::Element(id) => serde_json::json!({ ELEMENT_IDENTIFIER: id }),
+            Scope::Shadow(id) => serde_json::json!({ SHADOW_IDENTIFIER: id }),
+        };
+        let res: Vec<HashMap<String, String>> =
+            self.execute_script_with_atom(session_id, TEXT_SCRIPT, vec![scope, query])?;
+        Ok(res
+            .into_iter()
+            .filter_map(|f| f.into_iter().next())
+            .collect())
+    }
+
     pub(crate) fn get_active_element(&self, session_id: &str) -> SResult<(String, String)> {
         let res: ResponseWrapper<HashMap<String, String>> = self.req(Method::Get(format!(
             "{}/session/{}/element/active",
@@ -482,7 +541,7 @@ impl Http {
         for ele in res.value {
             return Ok(ele);
         }
-        Err(SError::Browser("element not found".to_string()))
+        Err(SError::no_such_element())
     }
 
     pub(crate) fn get_element_shadow_root(
@@ -506,6 +565,18 @@ impl Http {
         shadow_id: &str,
         by: By<'_>,
     ) -> SResult<(String, String)> {
+        if let Some(list) = by.alternatives() {
+            return find_any_of(&by, &list, |by| {
+                self.find_element_from_shadow_root(session_id, shadow_id, *by)
+            });
+        }
+        if let Some(query) = by.script_query() {
+            return self
+                .find_elements_by_script(session_id, Scope::Shadow(shadow_id), query)?
+                .into_iter()
+                .next()
+                .ok_or_else(SError::no_such_element);
+        }
         let res: ResponseWrapper<HashMap<String, String>> = self.req(Method::Post(
             format!(
                 "{}/session/{}/shadow/{}/element",
@@ -516,7 +587,7 @@ impl Http {
         for ele in res.value {
             return Ok(ele);
         }
-        Err(SError::Browser("element not found".to_string()))
+        Err(SError::no_such_element())
     }
 
     pub(crate) fn find_elements_from_shadow_root(
@@ -525,6 +596,14 @@ impl Http {
         shadow_id: &str,
         by: By<'_>,
     ) -> SResult<Vec<(String, String)>> {
+        if let Some(list) = by.alternatives() {
+            return find_all_any_of(&by, &list, |by| {
+                self.find_elements_from_shadow_root(session_id, shadow_id, *by)
+            });
+        }
+        if let Some(query) = by.script_query() {
+            return self.find_elements_by_script(session_id, Scope::Shadow(shadow_id), query);
+        }
         let res: ResponseWrapper<Vec<HashMap<String, String>>> = self.req(Method::Post(
             format!(
                 "{}/session/{}/shadow/{}/elements",
@@ -564,13 +643,13 @@ impl Http {
         Ok(res.value)
     }
 
-    pub(crate) fn get_element_property(
+    pub(crate) fn get_element_property<T: serde::de::DeserializeOwned>(
         &self,
         session_id: &str,
         element_id: &str,
         name: &str,
-    ) -> SResult<Option<String>> {
-        let res: ResponseWrapper<Option<String>> = self.req(Method::Get(format!(
+    ) -> SResult<Option<T>> {
+        let res: ResponseWrapper<Option<T>> = self.req(Method::Get(format!(
             "{}/session/{}/element/{}/property/{}",
             self.url, session_id, element_id, name
         )))?;
@@ -722,6 +801,88 @@ impl Http {
         Ok(res.value)
     }
 
+    ///
+    /// 参数可以是任意json，比如元素引用
+    pub(crate) fn execute_script_with<T: serde::de::DeserializeOwned>(
+        &self,
+        session_id: &str,
+        script: &str,
+        args: Vec<serde_json::Value>,
+    ) -> SResult<T> {
+        #[derive(Serialize)]
+        struct TempExecuteScript<'a> {
+            script: &'a str,
+            args: Vec<serde_json::Value>,
+        }
+        let t = TempExecuteScript { script, args };
+
+        let res: ResponseWrapper<T> = self.req(Method::Post(
+            format!("{}/session/{}/execute/sync", self.url, session_id),
+            serde_json::to_string(&t)?,
+        ))?;
+        Ok(res.value)
+    }
+
+    ///
+    /// 参数可以是任意json，比如元素引用
+    pub(crate) fn execute_async_script_with<T: serde::de::DeserializeOwned>(
+        &self,
+        session_id: &str,
+        script: &str,
+        args: Vec<serde_json::Value>,
+    ) -> SResult<T> {
+        #[derive(Serialize)]
+        struct TempExecuteScript<'a> {
+            script: &'a str,
+            args: Vec<serde_json::Value>,
+        }
+        let t = TempExecuteScript { script, args };
+
+        let res: ResponseWrapper<T> = self.req(Method::Post(
+            format!("{}/session/{}/execute/async", self.url, session_id),
+            serde_json::to_string(&t)?,
+        ))?;
+        Ok(res.value)
+    }
+
+    ///
+    /// 执行需要 `isDisplayed` 的脚本，脚本中可以直接使用 `isDisplayed` 变量
+    ///
+    /// 先使用页面中缓存的函数，页面中没有时才发送完整的 isDisplayed 脚本并缓存到页面中
+    pub(crate) fn execute_script_with_atom<T: serde::de::DeserializeOwned>(
+        &self,
+        session_id: &str,
+        script: &str,
+        args: Vec<serde_json::Value>,
+    ) -> SResult<T> {
+        let cached = format!(
+            "var isDisplayed = window.{IS_DISPLAYED_CACHE};\nif (typeof isDisplayed !== 'function') {{ return '{IS_DISPLAYED_MISSING}'; }}\n{script}"
+        );
+        let v: serde_json::Value = self.execute_script_with(session_id, &cached, args.clone())?;
+        if v.as_str() != Some(IS_DISPLAYED_MISSING) {
+            return Ok(serde_json::from_value(v)?);
+        }
+        self.execute_script_with(session_id, &install_atom(script), args)
+    }
+
+    /// 同 [Http::execute_script_with_atom]，执行异步脚本
+    pub(crate) fn execute_async_script_with_atom<T: serde::de::DeserializeOwned>(
+        &self,
+        session_id: &str,
+        script: &str,
+        args: Vec<serde_json::Value>,
+    ) -> SResult<T> {
+        let cached = format!(
+            "var isDisplayed = window.{IS_DISPLAYED_CACHE};\nif (typeof isDisplayed !== 'function') {{ arguments[arguments.length - 1]('{IS_DISPLAYED_MISSING}'); return; }}\n{script}"
+        );
+        let v: serde_json::Value =
+            self.execute_async_script_with(session_id, &cached, args.clone())?;
+        if v.as_str() != Some(IS_DISPLAYED_MISSING) {
+            return Ok(serde_json::from_value(v)?);
+        }
+        self.execute_async_script_with(session_id, &install_atom(script), args)
+    }
+
     pub(crate) fn set_timeouts(&self, session_id: &str, timeout: TimeoutType) -> SResult<()> {
         self.req_without_res(Method::Post(
             format!("{}/session/{}/timeouts", self.url, session_id),
@@ -768,6 +929,17 @@ impl Http {
         ))
     }
 
+    ///
+    /// 松开所有按下的键和按钮，清空输入状态
+    ///
+    /// https://w3c.github.io/webdriver/#release-actions
+    pub(crate) fn release_actions(&self, session_id: &str) -> SResult<()> {
+        self.req_without_res(Method::Delete(format!(
+            "{}/session/{}/actions",
+            self.url, session_id
+        )))
+    }
+
     pub(crate) fn dismiss_alert(&self, session_id: &str) -> SResult<()> {
         self.req_without_res(Method::Post(
             format!("{}/session/{}/alert/dismiss", self.url, session_id),
@@ -815,25 +987,122 @@ impl Http {
         Ok(base64::decode(res.value.as_bytes()))
     }
 
+    ///
+    /// 优先使用 `/displayed` 接口，driver 不支持时改用 isDisplayed 脚本，之后不再请求该接口
+    ///
+    /// https://w3c.github.io/webdriver/#element-displayedness
     pub(crate) fn is_element_displayed(&self, session_id: &str, element: Origin) -> SResult<bool> {
-        #[derive(Serialize)]
-        struct TempExecuteScript {
-            script: String,
-            args: Vec<Origin>,
+        if let (Origin::Element(_, id), true) =
+            (&element, self.native_displayed.get() != Some(false))
+        {
+            let res: SResult<ResponseWrapper<bool>> = self.req(Method::Get(format!(
+                "{}/session/{}/element/{}/displayed",
+                self.url, session_id, id
+            )));
+            match res {
+                Ok(res) => {
+                    self.native_displayed.set(Some(true));
+                    return Ok(res.value);
+                }
+                Err(e) if is_unsupported(&e) => {
+                    self.native_displayed.set(Some(false));
+                }
+                Err(e) => return Err(e),
+            }
         }
-        let t = TempExecuteScript {
-            script: format!(
-                "return ({}).apply(null, arguments);",
-                script::IS_DISPLAY_SCRIPT.to_string()
-            ),
-            args: vec![element],
-        };
+        self.execute_script_with_atom(
+            session_id,
+            "return isDisplayed(arguments[0]);",
+            vec![serde_json::to_value(element)?],
+        )
+    }
+}
 
-        let res: ResponseWrapper<bool> = self.req(Method::Post(
-            format!("{}/session/{}/execute/sync", self.url, session_id),
-            serde_json::to_string(&t)?,
-        ))?;
-        Ok(res.value)
+/// 发送完整的 isDisplayed 脚本并缓存到页面中，缓存的属性不可枚举，不会出现在页面的 `Object.keys(window)` 中
+fn install_atom(script: &str) -> String {
+    format!(
+        "var isDisplayed = {};\nObject.defineProperty(window, '{IS_DISPLAYED_CACHE}', {{ value: isDisplayed, configurable: true }});\n{script}",
+        script::IS_DISPLAY_SCRIPT
+    )
+}
+
+/// 驱动不支持该接口，包括代理等返回的没有 W3C 错误码的 404、405
+fn is_unsupported(e: &SError) -> bool {
+    match e.kind() {
+        ErrorKind::UnknownCommand => true,
+        ErrorKind::Other => matches!(e, SError::Http(404 | 405, _)),
+        _ => false,
+    }
+}
+
+/// 依次尝试 [By::AnyOf] 中的查找方式，返回第一个找到的元素
+fn find_any_of<T>(
+    by: &By<'_>,
+    list: &[By<'_>],
+    find: impl Fn(&By<'_>) -> SResult<T>,
+) -> SResult<T> {
+    let mut last = None;
+    for (i, alternative) in list.iter().enumerate() {
+        match find(alternative) {
+            Ok(v) => {
+                report_matched(by, i, alternative);
+                return Ok(v);
+            }
+            Err(e) if is_locator_error(&e) => {
+                log::debug!("{alternative} failed: {e}");
+                last = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Err(last.unwrap_or_else(SError::no_such_element))
+}
+
+/// 依次尝试 [By::AnyOf] 中的查找方式，返回第一个非空的结果
+fn find_all_any_of<T>(
+    by: &By<'_>,
+    list: &[By<'_>],
+    find: impl Fn(&By<'_>) -> SResult<Vec<T>>,
+) -> SResult<Vec<T>> {
+    let mut last = None;
+    let mut empty = false;
+    for (i, alternative) in list.iter().enumerate() {
+        match find(alternative) {
+            Ok(v) if !v.is_empty() => {
+                report_matched(by, i, alternative);
+                return Ok(v);
+            }
+            Ok(_) => {
+                log::debug!("{alternative} matched nothing");
+                empty = true;
+            }
+            Err(e) if is_locator_error(&e) => {
+                log::debug!("{alternative} failed: {e}");
+                last = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    // 所有方式都出错时才返回错误
+    match last {
+        Some(e) if !empty => Err(e),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// 选择器失效导致的错误，可以继续尝试下一个
+fn is_locator_error(e: &SError) -> bool {
+    matches!(
+        e.kind(),
+        ErrorKind::NoSuchElement | ErrorKind::InvalidSelector | ErrorKind::JavascriptError
+    )
+}
+
+fn report_matched(by: &By<'_>, index: usize, alternative: &By<'_>) {
+    if index == 0 {
+        log::debug!("{by} matched {alternative}");
+    } else {
+        log::warn!("{by} matched fallback #{index} {alternative}, earlier alternatives are broken");
     }
 }
 
@@ -858,14 +1127,135 @@ mod tests {
     use std::{collections::HashMap, net::TcpListener};
 
     use crate::{
+        actions::Origin,
         driver::Rect,
         http::Http,
         option::{FirefoxOption, MultipleTypeMapValue},
-        SError,
+        ErrorKind, SError,
     };
 
     use super::Capability;
 
+    /// 只处理一次请求的 http 服务，返回地址和收到的请求
+    fn serve_once(status: u16, body: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let (url, handle) = serve(vec![(status, body)]);
+        (
+            url,
+            std::thread::spawn(move || handle.join().unwrap().remove(0)),
+        )
+    }
+
+    /// 依次返回 `responses`，结束后返回收到的请求
+    fn serve(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            responses.into_iter().map(|(status, body)| {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+            write!(
+                reader.into_inner(),
+                "HTTP/1.1 {status} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+            }).collect()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_status_error() {
+        let body = r#"{"value":{"error":"no such window","message":"","stacktrace":""}}"#;
+        let (url, server) = serve_once(404, body);
+        let e = Http::new(&url, 5).delete_session("s").unwrap_err();
+        server.join().unwrap();
+        assert!(matches!(e, SError::Http(404, ref b) if b == body));
+
+        let (url, server) = serve_once(200, r#"{"value":null}"#);
+        Http::new(&url, 5).delete_session("s").unwrap();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_switch_to_frame() {
+        let (url, server) = serve_once(200, r#"{"value":null}"#);
+        Http::new(&url, 5)
+            .switch_to_frame("s", crate::driver::SwitchToFrame::Element("e1".to_string()))
+            .unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /session/s/frame HTTP/1.1"));
+        assert!(request.ends_with(r#"{"id":{"element-6066-11e4-a52e-4f735466cecf":"e1"}}"#));
+    }
+
+    #[test]
+    fn test_displayed_fallback() {
+        // 代理返回没有 W3C 错误码的 404 时改用脚本判断，之后不再请求 /displayed
+        let (url, server) = serve(vec![
+            (404, "Not Found"),
+            (200, r#"{"value":true}"#),
+            (200, r#"{"value":false}"#),
+        ]);
+        let http = Http::new(&url, 5);
+        let element = || Origin::Element(super::ELEMENT_IDENTIFIER.to_string(), "e1".to_string());
+        assert!(http.is_element_displayed("s", element()).unwrap());
+        assert!(!http.is_element_displayed("s", element()).unwrap());
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /session/s/element/e1/displayed HTTP/1.1"));
+        assert!(requests[1].starts_with("POST /session/s/execute/sync HTTP/1.1"));
+        assert!(requests[2].starts_with("POST /session/s/execute/sync HTTP/1.1"));
+
+        let body = r#"{"value":{"error":"no such element","message":"","stacktrace":""}}"#;
+        let (url, server) = serve_once(404, body);
+        let e = Http::new(&url, 5)
+            .is_element_displayed("s", element())
+            .unwrap_err();
+        server.join().unwrap();
+        assert_eq!(ErrorKind::NoSuchElement, e.kind());
+    }
+
+    #[test]
+    fn test_session_script_timeout() {
+        let session: crate::driver::Session = serde_json::from_str(
+            r#"{"sessionId":"s","capabilities":{"timeouts":{"implicit":0,"script":30000}}}"#,
+        )
+        .unwrap();
+        assert_eq!(Some(30000), session.script_timeout.get());
+        let session: crate::driver::Session = serde_json::from_str(
+            r#"{"sessionId":"s","capabilities":{"timeouts":{"script":null}}}"#,
+        )
+        .unwrap();
+        assert_eq!(None, session.script_timeout.get());
+    }
+
+    #[test]
+    fn test_switch_to_parent_frame() {
+        let (url, server) = serve_once(200, r#"{"value":null}"#);
+        Http::new(&url, 5).switch_to_parent_frame("s").unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /session/s/frame/parent HTTP/1.1"));
+    }
+
     #[test]
     fn test_capability() {
         let r = Rect {
@@ -1009,4 +1399,119 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_by() {
+        use crate::{By, TextQuery};
+        assert_eq!(
+            r##"{"using":"css selector","value":"#form\\:user\\.name"}"##,
+            serde_json::to_string(&By::Id("form:user.name")).unwrap()
+        );
+        assert_eq!(
+            r#"{"using":"css selector","value":".\\31 st"}"#,
+            serde_json::to_string(&By::Class("1st")).unwrap()
+        );
+        assert_eq!(
+            r#"{"using":"css selector","value":"[name=\"user\"]"}"#,
+            serde_json::to_string(&By::Name("user")).unwrap()
+        );
+        assert_eq!(
+            r#"{"using":"css selector","value":"[data-testid=\"a\\\"b\"]"}"#,
+            serde_json::to_string(&By::Attribute("data-testid", "a\"b")).unwrap()
+        );
+        assert_eq!(
+            r#"{"using":"xpath","value":"//a"}"#,
+            serde_json::to_string(&By::XPath("//a")).unwrap()
+        );
+        // 由脚本查找
+        assert!(serde_json::to_string(&By::text("save")).is_err());
+        assert_eq!(
+            r#"{"ignore_case":true,"kind":"contains","value":"save"}"#,
+            By::text_contains(TextQuery::ignore_case("save"))
+                .script_query()
+                .unwrap()
+                .to_string()
+        );
+        assert!(By::Css("a").script_query().is_none());
+    }
+
+    #[test]
+    fn test_any_of() {
+        use super::{find_all_any_of, find_any_of};
+        use crate::{driver::ByBuf, By};
+
+        let not_found = || SError::no_such_element();
+        let list = [By::Id("a"), By::Id("b"), By::Id("c")];
+        let by = By::any_of(&list);
+        assert_eq!("any_of=[id=a, id=b, id=c]", by.to_string());
+        let v = find_any_of(&by, &list, |f| match f {
+            By::Id("b") | By::Id("c") => Ok(f.to_string()),
+            _ => Err(not_found()),
+        });
+        assert_eq!("id=b", v.unwrap());
+        // 非查找相关的错误直接返回
+        let v = find_any_of(&by, &list, |f| match f {
+            By::Id("a") => Err(SError::Http(-1, "closed".to_string())),
+            _ => Ok(()),
+        });
+        assert!(matches!(v, Err(SError::Http(-1, _))));
+
+        let v = find_all_any_of(&by, &list, |_| Err::<Vec<()>, _>(not_found()));
+        assert!(v.is_err());
+        let v = find_all_any_of(&by, &list, |f| match f {
+            By::Id("a") => Ok(Vec::new()),
+            _ => Err(not_found()),
+        });
+        assert_eq!(Vec::<()>::new(), v.unwrap());
+
+        let buf: ByBuf =
+            serde_json::from_str(r#"{"any_of":[{"id":"a"},{"xpath":"//b"}]}"#).unwrap();
+        assert_eq!(
+            ByBuf::AnyOf(vec![
+                ByBuf::Id("a".to_string()),
+                ByBuf::XPath("//b".to_string())
+            ]),
+            buf
+        );
+        assert_eq!("any_of=[id=a, xpath=//b]", buf.to_string());
+        assert_eq!(buf, ByBuf::from(buf.as_by()));
+        assert_eq!(By::any_of(&[By::Id("a"), By::XPath("//b")]), buf.as_by());
+        assert!(serde_json::to_string(&by).is_err());
+    }
+
+    #[test]
+    fn test_error_kind() {
+        let e = SError::Http(
+            404,
+            r#"{"value":{"error":"no such element","message":"Unable to locate element","stacktrace":""}}"#
+                .to_string(),
+        );
+        assert_eq!(ErrorKind::NoSuchElement, e.kind());
+        let e = SError::Http(
+            404,
+            r#"{"value":{"error":"stale element reference","message":"","stacktrace":""}}"#
+                .to_string(),
+        );
+        assert_eq!(ErrorKind::StaleElementReference, e.kind());
+        let e = SError::Http(
+            400,
+            r#"{"value":{"error":"element click intercepted","message":""}}"#.to_string(),
+        );
+        assert_eq!(ErrorKind::ElementClickIntercepted, e.kind());
+        // 代理或者不支持的接口返回的 404 不是查找失败
+        assert_eq!(ErrorKind::Other, SError::Http(404, "".to_string()).kind());
+        assert_eq!(
+            ErrorKind::Other,
+            SError::Browser("element not found".to_string()).kind()
+        );
+        assert_eq!(ErrorKind::NoSuchElement, SError::no_such_element().kind());
+        assert_eq!(
+            ErrorKind::Other,
+            SError::Http(500, "error".to_string()).kind()
+        );
+        assert_eq!(
+            ErrorKind::Timeout,
+            SError::Timeout("wait timeout".to_string()).kind()
+        );
+    }
 }
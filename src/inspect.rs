@@ -0,0 +1,166 @@
+//!
+//! 批量读取元素信息
+//!
+//! 逐个调用 [Element::get_text]、[Element::get_attribute] 时每个字段都是一次 http 请求，
+//! [Driver::inspect](crate::driver::Driver::inspect) 和 [Element::snapshot] 在一次脚本调用中读取所有字段
+//!
+//! ```no_run
+//! use selenium::option::FirefoxBuilder;
+//! use selenium::driver::Driver;
+//! use selenium::By;
+//! let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+//! let links = driver.find_elements(By::Css("a")).unwrap();
+//! for row in driver.inspect(&links, &["text", "attr:href", "rect"]).unwrap() {
+//!     println!("{:?} {:?}", row.text, row.attr("href"));
+//! }
+//! ```
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{driver::Rect, element::Element, SError, SResult};
+
+const INSPECT_SCRIPT: &str = include_str!("js/inspect.js");
+
+/// 要读取的字段
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "kind", content = "name", rename_all = "snake_case")]
+pub enum Field {
+    /// 可见文字，同 [Element::get_text]
+    Text,
+    TagName,
+    Rect,
+    Displayed,
+    Enabled,
+    Selected,
+    Attribute(String),
+    /// 所有属性
+    AllAttributes,
+    /// 元素的 js 属性，类数组转为数组，对象取可枚举的字段，最多三层
+    Property(String),
+    /// 计算后的样式
+    Css(String),
+}
+
+/// 支持 `text`、`tag`、`rect`、`displayed`、`enabled`、`selected`、`attrs`，
+/// 以及 `attr:href`、`prop:value`、`css:color`
+impl FromStr for Field {
+    type Err = SError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let field = match s.split_once(':') {
+            Some(("attr", name)) => Field::Attribute(name.to_string()),
+            Some(("prop", name)) => Field::Property(name.to_string()),
+            Some(("css", name)) => Field::Css(name.to_string()),
+            Some(_) => return Err(SError::Message(format!("unknown field: {s}"))),
+            None => match s {
+                "text" => Field::Text,
+                "tag" | "tag_name" => Field::TagName,
+                "rect" => Field::Rect,
+                "displayed" => Field::Displayed,
+                "enabled" => Field::Enabled,
+                "selected" => Field::Selected,
+                "attrs" => Field::AllAttributes,
+                _ => return Err(SError::Message(format!("unknown field: {s}"))),
+            },
+        };
+        Ok(field)
+    }
+}
+
+/// [Driver::inspect](crate::driver::Driver::inspect) 的结果，没有请求的字段为 `None` 或者为空
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Inspection {
+    pub text: Option<String>,
+    pub tag_name: Option<String>,
+    pub rect: Option<Rect>,
+    pub displayed: Option<bool>,
+    pub enabled: Option<bool>,
+    pub selected: Option<bool>,
+    /// 属性不存在时为 `None`
+    pub attributes: HashMap<String, Option<String>>,
+    pub properties: HashMap<String, serde_json::Value>,
+    pub css: HashMap<String, String>,
+}
+
+impl Inspection {
+    /// 属性值，没有请求或者属性不存在时为 `None`
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name)?.as_deref()
+    }
+
+    pub fn property<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        serde_json::from_value(self.properties.get(name)?.clone()).ok()
+    }
+}
+
+/// 元素在某一时刻的状态
+#[derive(Debug, Clone, Deserialize)]
+pub struct Snapshot {
+    pub tag_name: String,
+    pub text: String,
+    pub rect: Rect,
+    pub displayed: bool,
+    pub enabled: bool,
+    pub selected: bool,
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl Snapshot {
+    pub(crate) const FIELDS: [Field; 7] = [
+        Field::TagName,
+        Field::Text,
+        Field::Rect,
+        Field::Displayed,
+        Field::Enabled,
+        Field::Selected,
+        Field::AllAttributes,
+    ];
+}
+
+/// 同一个会话中的元素，每个元素按 `fields` 读取后反序列化为 `T`
+pub(crate) fn inspect<T: DeserializeOwned>(
+    elements: &[Element],
+    fields: &[Field],
+) -> SResult<Vec<T>> {
+    let Some(first) = elements.first() else {
+        return Ok(Vec::new());
+    };
+    let origins = elements.iter().map(|f| f.origin()).collect::<Vec<_>>();
+    first.http.execute_script_with_atom(
+        &first.session.session_id,
+        INSPECT_SCRIPT,
+        vec![
+            serde_json::to_value(origins)?,
+            serde_json::to_value(fields)?,
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Field;
+
+    #[test]
+    fn test_field() {
+        assert_eq!(Field::Text, "text".parse().unwrap());
+        assert_eq!(
+            Field::Attribute("href".to_string()),
+            "attr:href".parse().unwrap()
+        );
+        assert_eq!(
+            Field::Css("color".to_string()),
+            "css:color".parse().unwrap()
+        );
+        assert!("attribute:href".parse::<Field>().is_err());
+        assert!("size".parse::<Field>().is_err());
+        assert_eq!(
+            r#"[{"kind":"text"},{"kind":"attribute","name":"href"}]"#,
+            serde_json::to_string(&[Field::Text, Field::Attribute("href".to_string())]).unwrap()
+        );
+    }
+}
@@ -0,0 +1,89 @@
+// 依赖外部定义的 isDisplayed，元素可操作时返回 null，否则返回原因
+var element = arguments[0];
+var checks = arguments[1];
+var done = arguments[arguments.length - 1];
+
+function describe(node) {
+  if (!node || !node.tagName) {
+    return String(node);
+  }
+  var s = node.tagName.toLowerCase();
+  if (node.id) {
+    s += '#' + node.id;
+  }
+  if (typeof node.className === 'string' && node.className.trim()) {
+    s += '.' + node.className.trim().split(/\s+/).join('.');
+  }
+  return s;
+}
+
+function rectOf() {
+  var r = element.getBoundingClientRect();
+  return [r.x, r.y, r.width, r.height].join(',');
+}
+
+// 命中测试需要穿过 shadow root
+function hitTarget(x, y) {
+  var root = document;
+  var hit = root.elementFromPoint(x, y);
+  while (hit && hit.shadowRoot) {
+    var inner = hit.shadowRoot.elementFromPoint(x, y);
+    if (!inner || inner === hit) {
+      break;
+    }
+    hit = inner;
+  }
+  return hit;
+}
+
+function contains(node) {
+  while (node) {
+    if (node === element) {
+      return true;
+    }
+    node = node.parentNode || node.host;
+  }
+  return false;
+}
+
+function afterChecks() {
+  if (checks.hit) {
+    var r = element.getBoundingClientRect();
+    var x = r.left + r.width / 2;
+    var y = r.top + r.height / 2;
+    if (x < 0 || y < 0 || x > window.innerWidth || y > window.innerHeight) {
+      element.scrollIntoView({ block: 'center', inline: 'center' });
+      r = element.getBoundingClientRect();
+      x = r.left + r.width / 2;
+      y = r.top + r.height / 2;
+    }
+    var hit = hitTarget(x, y);
+    if (!contains(hit)) {
+      return done('obscured by ' + describe(hit));
+    }
+  }
+  done(null);
+}
+
+if (!element.isConnected) {
+  done('detached');
+} else if (!isDisplayed(element)) {
+  done('not visible');
+} else if (checks.enabled && element.matches(':disabled')) {
+  done('disabled');
+} else if (checks.stable) {
+  var before = rectOf();
+  requestAnimationFrame(function () {
+    requestAnimationFrame(function () {
+      if (!element.isConnected) {
+        done('detached');
+      } else if (rectOf() !== before) {
+        done('not stable');
+      } else {
+        afterChecks();
+      }
+    });
+  });
+} else {
+  afterChecks();
+}
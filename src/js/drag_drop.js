@@ -0,0 +1,64 @@
+// 模拟 html5 拖放，原生的 actions 不会触发 drag 事件
+// arguments[0] 为拖动的元素，拖入文件时为 null，arguments[1] 为放置的元素，arguments[2] 为文件，内容是 base64
+var source = arguments[0];
+var target = arguments[1];
+var dataTransfer = new DataTransfer();
+(arguments[2] || []).forEach(function (f) {
+  var binary = atob(f.content);
+  var content = new Uint8Array(binary.length);
+  for (var i = 0; i < binary.length; i++) {
+    content[i] = binary.charCodeAt(i);
+  }
+  dataTransfer.items.add(new File([content], f.name, { type: f.mime }));
+});
+
+function fire(element, type) {
+  var r = element.getBoundingClientRect();
+  var event = new DragEvent(type, {
+    bubbles: true,
+    cancelable: type !== 'dragleave' && type !== 'dragend',
+    composed: true,
+    clientX: r.left + r.width / 2,
+    clientY: r.top + r.height / 2,
+    dataTransfer: dataTransfer
+  });
+  return element.dispatchEvent(event);
+}
+
+// 同浏览器，dragover 前根据 effectAllowed 设置默认的 dropEffect
+function defaultEffect() {
+  switch (dataTransfer.effectAllowed) {
+    case 'none':
+      return 'none';
+    case 'move':
+      return 'move';
+    case 'link':
+    case 'linkMove':
+      return 'link';
+    default:
+      return 'copy';
+  }
+}
+
+if (source) {
+  // dragstart 被取消时不会开始拖动
+  if (!fire(source, 'dragstart')) {
+    return false;
+  }
+  fire(source, 'drag');
+}
+dataTransfer.dropEffect = defaultEffect();
+fire(target, 'dragenter');
+dataTransfer.dropEffect = defaultEffect();
+// dragover 被取消才表示接受放置
+var accepted = !fire(target, 'dragover') && dataTransfer.dropEffect !== 'none';
+if (accepted) {
+  fire(target, 'drop');
+} else {
+  dataTransfer.dropEffect = 'none';
+  fire(target, 'dragleave');
+}
+if (source) {
+  fire(source, 'dragend');
+}
+return accepted;
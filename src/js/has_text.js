@@ -0,0 +1,10 @@
+// 只保留可见文字包含所有 texts 的元素，文字的处理同 get_text
+var elements = arguments[0];
+var texts = arguments[1];
+
+return elements.filter(function (element) {
+  var text = isDisplayed(element) ? (element.innerText || element.textContent || '') : '';
+  return texts.every(function (t) {
+    return text.indexOf(t) !== -1;
+  });
+});
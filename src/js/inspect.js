@@ -0,0 +1,93 @@
+// 一次读取多个元素的多个字段，fields 为 [{kind, name}]
+var elements = arguments[0];
+var fields = arguments[1];
+
+function text(element) {
+  if (!isDisplayed(element)) {
+    return '';
+  }
+  return (element.innerText || element.textContent || '')
+    .replace(/[ \t\f\r\u00a0]+/g, ' ')
+    .replace(/ ?\n ?/g, '\n')
+    .trim();
+}
+
+function rect(element) {
+  var r = element.getBoundingClientRect();
+  return {
+    x: r.left + window.pageXOffset,
+    y: r.top + window.pageYOffset,
+    width: r.width,
+    height: r.height
+  };
+}
+
+// 转为可以序列化的值，元素由驱动转为元素引用，类数组转为数组，其他对象取可枚举的字段
+function value(v, depth) {
+  depth = depth || 0;
+  if (v === undefined || v === null || typeof v === 'function' || typeof v === 'symbol') {
+    return null;
+  }
+  if (typeof v !== 'object') {
+    return v;
+  }
+  if (v instanceof Element) {
+    return v;
+  }
+  if (v instanceof Node || v === window || depth >= 3) {
+    return null;
+  }
+  if (Array.isArray(v) || typeof v.length === 'number') {
+    return Array.prototype.map.call(v, function (item) {
+      return value(item, depth + 1);
+    });
+  }
+  var result = {};
+  for (var key in v) {
+    if (typeof v[key] !== 'function') {
+      result[key] = value(v[key], depth + 1);
+    }
+  }
+  return result;
+}
+
+return elements.map(function (element) {
+  var result = { attributes: {}, properties: {}, css: {} };
+  fields.forEach(function (field) {
+    switch (field.kind) {
+      case 'text':
+        result.text = text(element);
+        break;
+      case 'tag_name':
+        result.tag_name = element.tagName.toLowerCase();
+        break;
+      case 'rect':
+        result.rect = rect(element);
+        break;
+      case 'displayed':
+        result.displayed = isDisplayed(element);
+        break;
+      case 'enabled':
+        result.enabled = !element.disabled && !(element.closest && element.closest('fieldset:disabled'));
+        break;
+      case 'selected':
+        result.selected = !!(element.checked || element.selected);
+        break;
+      case 'attribute':
+        result.attributes[field.name] = element.getAttribute(field.name);
+        break;
+      case 'all_attributes':
+        for (var i = 0; i < element.attributes.length; i++) {
+          result.attributes[element.attributes[i].name] = element.attributes[i].value;
+        }
+        break;
+      case 'property':
+        result.properties[field.name] = value(element[field.name], 0);
+        break;
+      case 'css':
+        result.css[field.name] = window.getComputedStyle(element).getPropertyValue(field.name);
+        break;
+    }
+  });
+  return result;
+});
@@ -0,0 +1,46 @@
+// 统计 fetch 和 XMLHttpRequest 的请求，重复执行不会重复注入
+var state = window.__seleniumNetwork;
+if (!state) {
+  state = window.__seleniumNetwork = { inflight: 0, last: Date.now() };
+  var begin = function () {
+    state.inflight++;
+    state.last = Date.now();
+  };
+  var end = function () {
+    state.inflight = Math.max(0, state.inflight - 1);
+    state.last = Date.now();
+  };
+
+  if (window.fetch) {
+    var originalFetch = window.fetch;
+    window.fetch = function () {
+      begin();
+      try {
+        return originalFetch.apply(this, arguments).then(function (response) {
+          end();
+          return response;
+        }, function (error) {
+          end();
+          throw error;
+        });
+      } catch (e) {
+        end();
+        throw e;
+      }
+    };
+  }
+
+  var originalSend = XMLHttpRequest.prototype.send;
+  XMLHttpRequest.prototype.send = function () {
+    begin();
+    this.addEventListener('loadend', end, { once: true });
+    try {
+      return originalSend.apply(this, arguments);
+    } catch (e) {
+      this.removeEventListener('loadend', end);
+      end();
+      throw e;
+    }
+  };
+}
+return { inflight: state.inflight, idle: Date.now() - state.last };
@@ -0,0 +1,36 @@
+// 用 elementFromPoint 检查中心和四个角，返回遮挡元素和被遮挡的点
+var element = arguments[0];
+var r = element.getBoundingClientRect();
+var root = element.getRootNode();
+var hit = root.elementFromPoint ? root : document;
+// 角往里缩1像素，避免落在边框外
+var inset = Math.min(1, r.width / 2, r.height / 2);
+var points = [
+  ['center', r.left + r.width / 2, r.top + r.height / 2],
+  ['top_left', r.left + inset, r.top + inset],
+  ['top_right', r.right - inset, r.top + inset],
+  ['bottom_left', r.left + inset, r.bottom - inset],
+  ['bottom_right', r.right - inset, r.bottom - inset]
+];
+
+function contains(target, node) {
+  for (var n = node; n; n = n.parentNode || n.host) {
+    if (n === target) {
+      return true;
+    }
+  }
+  return false;
+}
+
+var by = null;
+var covered = [];
+points.forEach(function (point) {
+  var top = hit.elementFromPoint(point[1], point[2]);
+  // 视口外的点无法检测
+  if (!top || contains(element, top)) {
+    return;
+  }
+  by = by || top;
+  covered.push(point[0]);
+});
+return { element: by ? [by] : [], points: covered };
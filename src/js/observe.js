@@ -0,0 +1,64 @@
+var condition = arguments[0];
+var done = arguments[arguments.length - 1];
+
+function textOf(element) {
+  return element.innerText !== undefined ? element.innerText : element.textContent;
+}
+
+var initial = null;
+if (condition.kind === 'text') {
+  initial = textOf(condition.element);
+} else if (condition.kind === 'attribute') {
+  initial = condition.element.getAttribute(condition.name);
+}
+
+// 满足条件时返回 {value: 结果}，否则返回 null
+function check() {
+  switch (condition.kind) {
+    case 'attached':
+      var found = document.querySelector(condition.selector);
+      return found ? { value: found } : null;
+    case 'detached':
+      return document.querySelector(condition.selector) ? null : { value: null };
+    case 'text':
+      return textOf(condition.element) !== initial ? { value: condition.element } : null;
+    case 'attribute':
+      return condition.element.getAttribute(condition.name) !== initial ? { value: condition.element } : null;
+  }
+  throw new Error('unsupported condition: ' + condition.kind);
+}
+
+// 上一次等待超时后留下的 observer
+if (window.__selenium_observer) {
+  window.__selenium_observer.disconnect();
+}
+
+var result = check();
+if (result) {
+  done(result.value);
+} else {
+  var target = condition.element || document.documentElement;
+  var timer = null;
+  var observer = new MutationObserver(function () {
+    var result = check();
+    if (result) {
+      clearTimeout(timer);
+      observer.disconnect();
+      done(result.value);
+    }
+  });
+  Object.defineProperty(window, '__selenium_observer', { value: observer, configurable: true, writable: true });
+  // 脚本超时后不再检查
+  if (condition.timeout) {
+    timer = setTimeout(function () {
+      observer.disconnect();
+    }, condition.timeout);
+  }
+  observer.observe(target, {
+    childList: true,
+    subtree: true,
+    characterData: true,
+    attributes: true,
+    attributeFilter: condition.kind === 'attribute' ? [condition.name] : undefined
+  });
+}
@@ -0,0 +1,48 @@
+// arguments[0] 为按普通查找方式找到的候选元素，arguments[1] 为位置条件
+var candidates = arguments[0];
+var filters = arguments[1];
+
+// 两个矩形最近边之间的距离，相交时为0
+function gap(a, b) {
+  var dx = Math.max(0, a.left - b.right, b.left - a.right);
+  var dy = Math.max(0, a.top - b.bottom, b.top - a.bottom);
+  return Math.sqrt(dx * dx + dy * dy);
+}
+
+function center(r) {
+  return { x: r.left + r.width / 2, y: r.top + r.height / 2 };
+}
+
+var checks = {
+  above: function (r, a) { return r.bottom <= a.top; },
+  below: function (r, a) { return r.top >= a.bottom; },
+  left: function (r, a) { return r.right <= a.left; },
+  right: function (r, a) { return r.left >= a.right; },
+  near: function (r, a, distance) { return gap(r, a) <= distance; }
+};
+
+var anchors = filters.map(function (f) {
+  return f.anchor.getBoundingClientRect();
+});
+
+var found = candidates.filter(function (element) {
+  var rect = element.getBoundingClientRect();
+  if (rect.width === 0 && rect.height === 0) {
+    return false;
+  }
+  return filters.every(function (f, i) {
+    return element !== f.anchor && checks[f.kind](rect, anchors[i], f.distance);
+  });
+});
+
+// 与selenium一致，按照与第一个参照元素的距离排序
+if (anchors.length > 0) {
+  var origin = center(anchors[0]);
+  var distanceOf = function (element) {
+    var c = center(element.getBoundingClientRect());
+    return Math.sqrt(Math.pow(c.x - origin.x, 2) + Math.pow(c.y - origin.y, 2));
+  };
+  found.sort(function (a, b) { return distanceOf(a) - distanceOf(b); });
+}
+
+return found;
@@ -0,0 +1,39 @@
+// 依赖外部定义的 isDisplayed，按可见文字查找元素，只返回最内层的匹配元素
+var scope = arguments[0] || document;
+var query = arguments[1];
+
+function normalize(s) {
+  return (s || '').replace(/\s+/g, ' ').trim();
+}
+
+var matcher;
+if (query.kind === 'regex') {
+  var re = new RegExp(query.value, query.ignore_case ? 'i' : '');
+  matcher = function (text) {
+    return re.test(text);
+  };
+} else {
+  var fold = function (s) {
+    return query.ignore_case ? s.toLowerCase() : s;
+  };
+  var expected = fold(normalize(query.value));
+  matcher = function (text) {
+    text = fold(text);
+    return query.kind === 'exact' ? text === expected : text.indexOf(expected) !== -1;
+  };
+}
+
+var matches = [];
+var all = scope.querySelectorAll('*');
+for (var i = 0; i < all.length; i++) {
+  var element = all[i];
+  var text = element.innerText !== undefined ? element.innerText : element.textContent;
+  if (matcher(normalize(text)) && isDisplayed(element)) {
+    matches.push(element);
+  }
+}
+return matches.filter(function (element) {
+  return !matches.some(function (other) {
+    return other !== element && element.contains(other);
+  });
+});
@@ -0,0 +1,153 @@
+// 生成能唯一定位到元素的选择器，mode 为 css 或 xpath
+var element = arguments[0];
+var mode = arguments[1];
+var css = mode === 'css';
+// 从稳定到不稳定
+var LEVELS = ['id', 'test_id', 'attribute', 'class', 'tag', 'positional'];
+var TEST_ATTRIBUTES = ['data-testid', 'data-test-id', 'data-test', 'data-cy', 'data-qa'];
+var HTML = 'http://www.w3.org/1999/xhtml';
+
+var root = element.getRootNode();
+if (!css && root !== document) {
+  throw new Error('xpath can not locate elements inside shadow root');
+}
+
+function cssString(v) {
+  return '"' + v.replace(/\\/g, '\\\\').replace(/"/g, '\\"').replace(/\n/g, '\\a ') + '"';
+}
+
+function xpathString(v) {
+  if (v.indexOf("'") === -1) {
+    return "'" + v + "'";
+  }
+  if (v.indexOf('"') === -1) {
+    return '"' + v + '"';
+  }
+  return 'concat(' + v.split("'").map(function (p) {
+    return "'" + p + "'";
+  }).join(', "\'", ') + ')';
+}
+
+function tagOf(node) {
+  if (css) {
+    return CSS.escape(node.localName);
+  }
+  return node.namespaceURI === HTML ? node.localName : "*[local-name()=" + xpathString(node.localName) + "]";
+}
+
+// 元素自身可用的选择器，按稳定程度排列
+function candidates(node) {
+  var tag = tagOf(node);
+  var list = [];
+  var attribute = function (name, level, prefix) {
+    var v = node.getAttribute(name);
+    if (v) {
+      list.push({
+        expr: css
+          ? prefix + '[' + CSS.escape(name) + '=' + cssString(v) + ']'
+          : tag + '[@' + name + '=' + xpathString(v) + ']',
+        level: level
+      });
+    }
+  };
+  if (node.id) {
+    list.push({ expr: css ? '#' + CSS.escape(node.id) : tag + '[@id=' + xpathString(node.id) + ']', level: 'id' });
+  }
+  TEST_ATTRIBUTES.forEach(function (name) {
+    attribute(name, 'test_id', '');
+  });
+  attribute('name', 'attribute', tag);
+  attribute('aria-label', 'attribute', tag);
+  var classes = Array.prototype.slice.call(node.classList);
+  var byClass = function (c) {
+    return css
+      ? '.' + CSS.escape(c)
+      : "[contains(concat(' ', normalize-space(@class), ' '), " + xpathString(' ' + c + ' ') + ')]';
+  };
+  classes.forEach(function (c) {
+    list.push({ expr: tag + byClass(c), level: 'class' });
+  });
+  if (classes.length > 1) {
+    list.push({ expr: tag + classes.map(byClass).join(''), level: 'class' });
+  }
+  list.push({ expr: tag, level: 'tag' });
+  return list;
+}
+
+function positional(node, parent) {
+  var siblings = Array.prototype.slice.call(parent.children);
+  if (css) {
+    return { expr: tagOf(node) + ':nth-child(' + (siblings.indexOf(node) + 1) + ')', level: 'positional' };
+  }
+  var same = siblings.filter(function (f) {
+    return f.localName === node.localName && f.namespaceURI === node.namespaceURI;
+  });
+  return { expr: tagOf(node) + '[' + (same.indexOf(node) + 1) + ']', level: 'positional' };
+}
+
+function join(parts) {
+  return css ? parts.join(' > ') : '//' + parts.join('/');
+}
+
+function select(expr, context) {
+  if (css) {
+    return Array.prototype.slice.call(context.querySelectorAll(expr));
+  }
+  var result = document.evaluate(expr, context, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null);
+  var nodes = [];
+  for (var i = 0; i < result.snapshotLength; i++) {
+    nodes.push(result.snapshotItem(i));
+  }
+  return nodes;
+}
+
+function unique(expr) {
+  var nodes = select(expr, root);
+  return nodes.length === 1 && nodes[0] === element;
+}
+
+function uniqueAmongSiblings(node, parent, candidate) {
+  var nodes = css
+    ? Array.prototype.slice.call(parent.children).filter(function (f) {
+      return f.matches(candidate.expr);
+    })
+    : select('./' + candidate.expr, parent);
+  return nodes.length === 1 && nodes[0] === node;
+}
+
+function result(expr, weakest) {
+  return { selector: expr, specificity: LEVELS[weakest] };
+}
+
+var parts = [];
+var weakest = 0;
+var node = element;
+while (true) {
+  var list = candidates(node);
+  for (var i = 0; i < list.length; i++) {
+    var expr = join([list[i].expr].concat(parts));
+    if (unique(expr)) {
+      return result(expr, Math.max(weakest, LEVELS.indexOf(list[i].level)));
+    }
+  }
+  var parent = node.parentNode;
+  var part = null;
+  for (var j = 0; j < list.length; j++) {
+    if (uniqueAmongSiblings(node, parent, list[j])) {
+      part = list[j];
+      break;
+    }
+  }
+  part = part || positional(node, parent);
+  weakest = Math.max(weakest, LEVELS.indexOf(part.level));
+  parts.unshift(part.expr);
+  if (parent.nodeType !== Node.ELEMENT_NODE) {
+    // shadow root 中最外层的元素
+    var last = join(parts);
+    if (unique(last)) {
+      return result(last, weakest);
+    }
+    throw new Error('can not build a unique selector');
+  }
+  node = parent;
+}
@@ -0,0 +1,31 @@
+// 元素在视口中可见部分的比例，会被 overflow 不为 visible 的祖先元素裁剪
+var element = arguments[0];
+var r = element.getBoundingClientRect();
+var width = window.innerWidth || document.documentElement.clientWidth;
+var height = window.innerHeight || document.documentElement.clientHeight;
+var left = Math.max(r.left, 0);
+var top = Math.max(r.top, 0);
+var right = Math.min(r.right, width);
+var bottom = Math.min(r.bottom, height);
+
+for (var p = element.parentElement || (element.getRootNode().host || null); p; p = p.parentElement || (p.getRootNode().host || null)) {
+  if (p === document.documentElement || p === document.body) {
+    continue;
+  }
+  var style = window.getComputedStyle(p);
+  if (style.overflowX !== 'visible' || style.overflowY !== 'visible') {
+    var c = p.getBoundingClientRect();
+    left = Math.max(left, c.left);
+    top = Math.max(top, c.top);
+    right = Math.min(right, c.right);
+    bottom = Math.min(bottom, c.bottom);
+  }
+}
+
+var area = r.width * r.height;
+var visible = Math.max(right - left, 0) * Math.max(bottom - top, 0);
+return {
+  ratio: area > 0 ? Math.min(visible / area, 1) : 0,
+  rect: r.toJSON(),
+  viewport: { width: width, height: height }
+};
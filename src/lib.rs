@@ -11,8 +11,10 @@ pub enum SError {
     Message(String),
     /// http通信错误，比如连接失败，参数格式错误等等
     Http(i32, String),
-    /// http请求成功，但是对应参数不正确，比如找不到元素
+    /// http请求成功，但是对应参数不正确
     Browser(String),
+    /// 本地产生的查找失败，[SError::kind] 返回其中的类型
+    NotFound(ErrorKind, String),
     /// 超时
     Timeout(String),
     Io(std::io::Error),
@@ -24,6 +26,7 @@ impl Display for SError {
             Self::Driver(m) => f.write_fmt(format_args!("driver:{m}")),
             Self::Http(status, m) => f.write_fmt(format_args!("http:status:{status}, reason:{m}")),
             Self::Browser(m) => f.write_fmt(format_args!("browser:{m}")),
+            Self::NotFound(_, m) => f.write_fmt(format_args!("not found:{m}")),
             Self::Timeout(m) => f.write_fmt(format_args!("timeout:{m}")),
             Self::Io(m) => f.write_fmt(format_args!("io:{m}")),
         }
@@ -37,6 +40,9 @@ impl Debug for SError {
             Self::Message(arg0) => f.debug_tuple("Message").field(arg0).finish(),
             Self::Http(arg0, arg1) => f.debug_tuple("Http").field(arg0).field(arg1).finish(),
             Self::Browser(arg0) => f.debug_tuple("Browser").field(arg0).finish(),
+            Self::NotFound(arg0, arg1) => {
+                f.debug_tuple("NotFound").field(arg0).field(arg1).finish()
+            }
             Self::Timeout(arg0) => f.debug_tuple("Timeout").field(arg0).finish(),
             Self::Io(arg0) => f.debug_tuple("Io").field(arg0).finish(),
         }
@@ -51,18 +57,115 @@ impl From<std::io::Error> for SError {
 
 impl std::error::Error for SError {}
 
+///
+/// webdriver 返回的错误码
+///
+/// https://w3c.github.io/webdriver/#errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    NoSuchElement,
+    StaleElementReference,
+    ElementNotInteractable,
+    ElementClickIntercepted,
+    InvalidSelector,
+    InvalidArgument,
+    NoSuchFrame,
+    NoSuchWindow,
+    NoSuchAlert,
+    UnexpectedAlertOpen,
+    JavascriptError,
+    ScriptTimeout,
+    Timeout,
+    UnknownCommand,
+    /// 其他错误
+    Other,
+}
+
+impl ErrorKind {
+    fn from_code(code: &str) -> Self {
+        match code {
+            "no such element" => Self::NoSuchElement,
+            "stale element reference" => Self::StaleElementReference,
+            "element not interactable" => Self::ElementNotInteractable,
+            "element click intercepted" => Self::ElementClickIntercepted,
+            "invalid selector" => Self::InvalidSelector,
+            "invalid argument" => Self::InvalidArgument,
+            "no such frame" => Self::NoSuchFrame,
+            "no such window" => Self::NoSuchWindow,
+            "no such alert" => Self::NoSuchAlert,
+            "unexpected alert open" => Self::UnexpectedAlertOpen,
+            "javascript error" => Self::JavascriptError,
+            "script timeout" => Self::ScriptTimeout,
+            "timeout" => Self::Timeout,
+            "unknown command" | "unknown method" => Self::UnknownCommand,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl SError {
+    /// 错误类型，根据 http 响应中 w3c 的 `error` 字段判断，[SError::NotFound] 为其中的类型，其他错误为 [ErrorKind::Other]
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Http(status, body) => {
+                #[derive(serde::Deserialize)]
+                struct ErrorValue {
+                    error: String,
+                }
+                #[derive(serde::Deserialize)]
+                struct ErrorResponse {
+                    value: ErrorValue,
+                }
+                match serde_json::from_str::<ErrorResponse>(body) {
+                    Ok(v) => ErrorKind::from_code(&v.value.error),
+                    Err(_) => ErrorKind::Other,
+                }
+            }
+            Self::NotFound(kind, _) => *kind,
+            Self::Timeout(_) => ErrorKind::Timeout,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// 本地查找元素失败
+    pub(crate) fn no_such_element() -> Self {
+        SError::NotFound(ErrorKind::NoSuchElement, "element not found".to_string())
+    }
+}
+
 type SResult<T> = Result<T, SError>;
-mod actions;
+mod actionability;
+pub mod actions;
+pub mod css;
 pub mod driver;
 pub mod element;
+pub mod frame;
+pub mod gesture;
 pub(crate) mod http;
+pub mod inspect;
+pub mod locator;
 pub mod option;
+pub mod page;
+pub mod relative;
 pub mod shadow;
+pub mod wait;
+pub mod xpath;
 
 pub use actions::Key;
 pub use actions::Origin;
+pub use driver::AsBy;
 pub use driver::By;
+pub use driver::ByBuf;
+pub use driver::TextQuery;
 pub use driver::TimeoutType;
+pub use locator::Locator;
+pub use relative::locate_with;
+pub use relative::RelativeBy;
+#[cfg(feature = "derive")]
+pub use selenium_macros::PageObject;
+#[cfg(feature = "macros")]
+pub use selenium_macros::{css, xpath};
+pub use wait::Wait;
 
 pub mod base64 {
     use std::{collections::HashMap, sync::OnceLock};
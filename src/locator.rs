@@ -0,0 +1,290 @@
+//!
+//! 延迟查找的元素
+//!
+//! [Element] 保存的是查找时得到的 id，页面重新渲染后就会失效。
+//! [Locator] 只保存查找方式，每次操作时重新查找，遇到 stale element reference 时会再查找一次
+//!
+//! ```no_run
+//! use selenium::option::FirefoxBuilder;
+//! use selenium::driver::Driver;
+//! use selenium::By;
+//! let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+//! let items = driver.locator(By::Css("ul")).locator(By::Css("li"));
+//! items.filter("github").first().click().unwrap();
+//! for item in items.all().unwrap() {
+//!     println!("{}", item.get_text().unwrap());
+//! }
+//! ```
+//!
+//! [Locator::frame] 可以指定元素所在的 frame，每次操作时从顶层文档开始进入，结束后回到调用前所在的 frame；
+//! 没有指定 frame 的 Locator 不切换，在当前所在的 frame 中查找
+use std::{collections::HashMap, fmt::Debug, rc::Rc};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    driver::{AsBy, ByBuf, Rect, Session},
+    element::Element,
+    frame::{self, FramePath},
+    http::Http,
+    shadow::Shadow,
+    By, ErrorKind, SError, SResult,
+};
+
+const HAS_TEXT_SCRIPT: &str = include_str!("js/has_text.js");
+
+#[derive(Clone)]
+pub struct Locator {
+    pub(crate) http: Rc<Http>,
+    pub(crate) session: Rc<Session>,
+    /// `None` 表示从页面开始查找
+    parent: Option<Box<Locator>>,
+    /// 查找前依次进入的 frame，只在最外层的 Locator 上保存
+    frames: FramePath,
+    by: ByBuf,
+    /// 按文字过滤
+    has_text: Vec<String>,
+    /// 过滤之后的第几个
+    index: Option<usize>,
+}
+
+impl Debug for Locator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Locator")
+            .field("parent", &self.parent)
+            .field("frames", &self.frames)
+            .field("by", &self.by)
+            .field("has_text", &self.has_text)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl Locator {
+    pub(crate) fn new(http: Rc<Http>, session: Rc<Session>, by: By<'_>) -> Self {
+        Locator {
+            http,
+            session,
+            parent: None,
+            frames: FramePath::new(),
+            by: by.as_by().into(),
+            has_text: Vec::new(),
+            index: None,
+        }
+    }
+
+    /// 在当前元素内继续查找
+    pub fn locator<T: AsBy>(&self, by: T) -> Locator {
+        Locator {
+            http: Rc::clone(&self.http),
+            session: Rc::clone(&self.session),
+            parent: Some(Box::new(self.clone())),
+            frames: FramePath::new(),
+            by: by.as_by().into(),
+            has_text: Vec::new(),
+            index: None,
+        }
+    }
+
+    ///
+    /// 查找前先进入 `by` 对应的 frame，多次调用依次进入嵌套的 frame
+    ///
+    /// frame 从页面顶层开始进入，操作结束后回到调用前所在的 frame
+    pub fn frame<T: AsBy>(mut self, by: T) -> Self {
+        match self.parent.take() {
+            Some(parent) => self.parent = Some(Box::new(parent.frame(by))),
+            None => self.frames = self.frames.then(by),
+        }
+        self
+    }
+
+    /// 替换整个 frame 路径
+    pub fn frames(mut self, path: FramePath) -> Self {
+        match self.parent.take() {
+            Some(parent) => self.parent = Some(Box::new(parent.frames(path))),
+            None => self.frames = path,
+        }
+        self
+    }
+
+    /// 只保留文字中包含 `text` 的元素
+    pub fn filter(&self, text: &str) -> Locator {
+        let mut locator = self.clone();
+        locator.has_text.push(text.to_string());
+        locator
+    }
+
+    /// 第 `index` 个元素，从0开始
+    pub fn nth(&self, index: usize) -> Locator {
+        let mut locator = self.clone();
+        locator.index = Some(index);
+        locator
+    }
+
+    pub fn first(&self) -> Locator {
+        self.nth(0)
+    }
+
+    pub fn count(&self) -> SResult<usize> {
+        self.in_frames(|| self.resolve_all().map(|v| v.len()))
+    }
+
+    /// 当前匹配的每个元素，每一个都是 [Locator::nth]
+    pub fn all(&self) -> SResult<Vec<Locator>> {
+        Ok((0..self.count()?).map(|i| self.nth(i)).collect())
+    }
+
+    ///
+    /// 立即查找元素
+    ///
+    /// 设置了 frame 时返回错误，操作结束后已经回到调用前的 frame，元素无法使用，
+    /// 需要元素时使用 [Driver::within_frames](crate::driver::Driver::within_frames)
+    pub fn element(&self) -> SResult<Element> {
+        if !self.frame_path().is_empty() {
+            return Err(SError::Message(
+                "element() is not available for a locator with frames".to_string(),
+            ));
+        }
+        self.resolve()
+    }
+
+    /// 查找前进入的 frame
+    pub fn frame_path(&self) -> &FramePath {
+        match &self.parent {
+            Some(parent) => parent.frame_path(),
+            None => &self.frames,
+        }
+    }
+
+    /// 设置了 frame 时从顶层进入，结束后回到调用前的 frame；否则在当前 frame 中执行
+    fn in_frames<T>(&self, f: impl FnOnce() -> SResult<T>) -> SResult<T> {
+        frame::within_current(&self.http, &self.session, self.frame_path(), f)
+    }
+
+    fn resolve_all(&self) -> SResult<Vec<Element>> {
+        let mut elements = match &self.parent {
+            Some(parent) => parent.resolve()?.find_elements(self.by.as_by())?,
+            None => self.find_from_driver()?,
+        };
+        if !self.has_text.is_empty() && !elements.is_empty() {
+            // 一次脚本调用过滤所有元素
+            let origins = elements.iter().map(|f| f.origin()).collect::<Vec<_>>();
+            let v: Vec<HashMap<String, String>> = self.http.execute_script_with_atom(
+                &self.session.session_id,
+                HAS_TEXT_SCRIPT,
+                vec![
+                    serde_json::to_value(origins)?,
+                    serde_json::to_value(&self.has_text)?,
+                ],
+            )?;
+            elements = v
+                .into_iter()
+                .filter_map(|f| f.into_iter().next())
+                .map(|f| self.element_from(f))
+                .collect();
+        }
+        Ok(elements)
+    }
+
+    fn resolve(&self) -> SResult<Element> {
+        if self.has_text.is_empty() && self.index.unwrap_or(0) == 0 {
+            return match &self.parent {
+                Some(parent) => parent.resolve()?.find_element(self.by.as_by()),
+                None => {
+                    let v = self
+                        .http
+                        .find_element(&self.session.session_id, &self.by.as_by())?;
+                    Ok(self.element_from(v))
+                }
+            };
+        }
+        self.resolve_all()?
+            .into_iter()
+            .nth(self.index.unwrap_or(0))
+            .ok_or_else(SError::no_such_element)
+    }
+
+    fn find_from_driver(&self) -> SResult<Vec<Element>> {
+        let v = self
+            .http
+            .find_elements(&self.session.session_id, &self.by.as_by())?;
+        Ok(v.into_iter().map(|f| self.element_from(f)).collect())
+    }
+
+    fn element_from(&self, v: (String, String)) -> Element {
+        Element {
+            http: Rc::clone(&self.http),
+            session: Rc::clone(&self.session),
+            identify: v.0,
+            id: v.1,
+        }
+    }
+
+    /// 查找元素并执行操作，元素失效时重新查找一次
+    fn run<T>(&self, op: impl Fn(&Element) -> SResult<T>) -> SResult<T> {
+        self.in_frames(|| match self.resolve().and_then(|e| op(&e)) {
+            Err(e) if e.kind() == ErrorKind::StaleElementReference => op(&self.resolve()?),
+            r => r,
+        })
+    }
+}
+
+impl Locator {
+    pub fn find_element<T: AsBy>(&self, by: T) -> SResult<Element> {
+        self.run(|e| e.find_element(&by))
+    }
+
+    pub fn find_elements<T: AsBy>(&self, by: T) -> SResult<Vec<Element>> {
+        self.run(|e| e.find_elements(&by))
+    }
+
+    pub fn get_shadow_root(&self) -> SResult<Shadow> {
+        self.run(|e| e.get_shadow_root())
+    }
+
+    pub fn is_selected(&self) -> SResult<bool> {
+        self.run(|e| e.is_selected())
+    }
+
+    pub fn get_attribute(&self, name: &str) -> SResult<Option<String>> {
+        self.run(|e| e.get_attribute(name))
+    }
+    pub fn get_property<T: DeserializeOwned>(&self, name: &str) -> SResult<Option<T>> {
+        self.run(|e| e.get_property(name))
+    }
+    pub fn get_css_value(&self, name: &str) -> SResult<String> {
+        self.run(|e| e.get_css_value(name))
+    }
+    pub fn get_text(&self) -> SResult<String> {
+        self.run(|e| e.get_text())
+    }
+
+    pub fn get_tag_name(&self) -> SResult<String> {
+        self.run(|e| e.get_tag_name())
+    }
+    pub fn get_rect(&self) -> SResult<Rect> {
+        self.run(|e| e.get_rect())
+    }
+    pub fn is_enabled(&self) -> SResult<bool> {
+        self.run(|e| e.is_enabled())
+    }
+    /// 左键点击元素
+    pub fn click(&self) -> SResult<()> {
+        self.run(|e| e.click())
+    }
+    pub fn clear(&self) -> SResult<()> {
+        self.run(|e| e.clear())
+    }
+    /// 发送key，可以当做键盘输入
+    pub fn send_keys(&self, key: &str) -> SResult<()> {
+        self.run(|e| e.send_keys(key))
+    }
+
+    pub fn take_screenshot(&self) -> SResult<Vec<u8>> {
+        self.run(|e| e.take_screenshot())
+    }
+
+    pub fn is_displayed(&self) -> SResult<bool> {
+        self.run(|e| e.is_displayed())
+    }
+}
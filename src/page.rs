@@ -0,0 +1,194 @@
+//!
+//! Page Object 支持
+//!
+//! 开启 `derive` features 后可以使用 `#[derive(PageObject)]` 自动生成元素访问方法
+//!
+//! ```ignore
+//! use selenium::driver::Driver;
+//! use selenium::element::Element;
+//! use selenium::page::{Component, Find, FindAll};
+//! use selenium::PageObject;
+//!
+//! #[derive(PageObject)]
+//! struct LoginPage<'a> {
+//!     #[root]
+//!     driver: &'a Driver,
+//!     #[find(css = "#user")]
+//!     user: Find,
+//!     #[find(id = "submit")]
+//!     #[wait(timeout_ms = 5000)]
+//!     submit: Find,
+//!     #[find_all(xpath = "//ul/li")]
+//!     items: FindAll,
+//!     #[find(css = "header")]
+//!     header: Component<Header>,
+//! }
+//!
+//! /// 组件的查找范围限定在父元素内
+//! #[derive(PageObject)]
+//! struct Header {
+//!     #[root]
+//!     root: Element,
+//!     #[find(class = "logo")]
+//!     logo: Find,
+//! }
+//!
+//! let page = LoginPage::new(&driver);
+//! page.user()?.send_keys("name")?;
+//! page.header()?.logo()?.click()?;
+//! ```
+//!
+//! 每次调用访问方法都会重新查找元素
+use std::{marker::PhantomData, thread::sleep, time::Duration};
+
+use crate::{driver::Driver, element::Element, By, SError, SResult};
+
+/// 能够查找元素的上下文，比如 [Driver] 和 [Element]
+pub trait SearchContext {
+    fn find_element(&self, by: By<'_>) -> SResult<Element>;
+
+    fn find_elements(&self, by: By<'_>) -> SResult<Vec<Element>>;
+
+    /// 等待直到元素出现
+    ///
+    /// # Params
+    ///
+    /// timeout 毫秒
+    fn wait_until_element(&self, by: By<'_>, timeout: u128) -> SResult<Element>;
+}
+
+impl SearchContext for Driver {
+    fn find_element(&self, by: By<'_>) -> SResult<Element> {
+        Driver::find_element(self, by)
+    }
+
+    fn find_elements(&self, by: By<'_>) -> SResult<Vec<Element>> {
+        Driver::find_elements(self, by)
+    }
+
+    fn wait_until_element(&self, by: By<'_>, timeout: u128) -> SResult<Element> {
+        Driver::wait_until_element(self, by, timeout)
+    }
+}
+
+impl SearchContext for Element {
+    fn find_element(&self, by: By<'_>) -> SResult<Element> {
+        Element::find_element(self, by)
+    }
+
+    fn find_elements(&self, by: By<'_>) -> SResult<Vec<Element>> {
+        Element::find_elements(self, by)
+    }
+
+    fn wait_until_element(&self, by: By<'_>, timeout: u128) -> SResult<Element> {
+        let start = std::time::Instant::now();
+        loop {
+            match Element::find_element(self, by) {
+                Ok(ele) => return Ok(ele),
+                Err(e) => {
+                    if start.elapsed().as_millis() > timeout {
+                        return Err(SError::Timeout(format!("{:?}", e)));
+                    }
+                }
+            }
+            sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+impl<T: SearchContext + ?Sized> SearchContext for &T {
+    fn find_element(&self, by: By<'_>) -> SResult<Element> {
+        (**self).find_element(by)
+    }
+
+    fn find_elements(&self, by: By<'_>) -> SResult<Vec<Element>> {
+        (**self).find_elements(by)
+    }
+
+    fn wait_until_element(&self, by: By<'_>, timeout: u128) -> SResult<Element> {
+        (**self).wait_until_element(by, timeout)
+    }
+}
+
+/// 由 `#[derive(PageObject)]` 实现
+pub trait PageObject: Sized {
+    /// `#[root]` 字段的类型，一般是 `&Driver` 或者 [Element]
+    type Root;
+
+    fn from_root(root: Self::Root) -> Self;
+}
+
+/// 标记字段，配合 `#[find(...)]` 生成返回 [Element] 的方法
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Find;
+
+impl Find {
+    /// 由生成的方法调用，`timeout` 为 `#[wait(...)]` 中的等待时间，单位毫秒
+    pub fn find<C: SearchContext + ?Sized>(
+        &self,
+        root: &C,
+        by: By<'_>,
+        timeout: Option<u128>,
+    ) -> SResult<Element> {
+        match timeout {
+            Some(timeout) => root.wait_until_element(by, timeout),
+            None => root.find_element(by),
+        }
+    }
+}
+
+/// 标记字段，配合 `#[find_all(...)]` 生成返回 `Vec<Element>` 的方法
+#[derive(Default, Debug, Clone, Copy)]
+pub struct FindAll;
+
+impl FindAll {
+    /// 同 [Find::find]，有等待时间时至少等到一个元素出现
+    pub fn find<C: SearchContext + ?Sized>(
+        &self,
+        root: &C,
+        by: By<'_>,
+        timeout: Option<u128>,
+    ) -> SResult<Vec<Element>> {
+        if let Some(timeout) = timeout {
+            root.wait_until_element(by, timeout)?;
+        }
+        root.find_elements(by)
+    }
+}
+
+/// 标记字段，生成的方法会以找到的元素作为根，构造出组件 `T`
+///
+/// `T` 也需要是 [PageObject]，且 `Root` 为 [Element]
+pub struct Component<T>(PhantomData<T>);
+
+impl<T> Default for Component<T> {
+    fn default() -> Self {
+        Component(PhantomData)
+    }
+}
+
+impl<T: PageObject<Root = Element>> Component<T> {
+    /// 同 [Find::find]，以找到的元素构造组件
+    pub fn find<C: SearchContext + ?Sized>(
+        &self,
+        root: &C,
+        by: By<'_>,
+        timeout: Option<u128>,
+    ) -> SResult<T> {
+        Find.find(root, by, timeout).map(T::from_root)
+    }
+
+    /// 同 [FindAll::find]，以找到的每个元素构造组件
+    pub fn find_all<C: SearchContext + ?Sized>(
+        &self,
+        root: &C,
+        by: By<'_>,
+        timeout: Option<u128>,
+    ) -> SResult<Vec<T>> {
+        Ok(FindAll
+            .find(root, by, timeout)?
+            .into_iter()
+            .map(T::from_root)
+            .collect())
+    }
+}
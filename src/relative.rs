@@ -0,0 +1,153 @@
+//!
+//! 相对定位，根据与其他元素的位置关系查找元素
+//!
+//! 候选元素按普通的查找方式查找，所有位置条件在一次脚本调用中完成计算，参考 selenium 4 的 `RelativeBy`
+use serde::{ser::SerializeStruct, Serialize};
+
+use crate::{element::Element, By, Origin, SResult};
+
+/// `near` 的默认距离，单位像素，与selenium保持一致
+pub const DEFAULT_NEAR_DISTANCE: u32 = 50;
+
+enum Direction {
+    Above,
+    Below,
+    LeftOf,
+    RightOf,
+    Near(u32),
+}
+
+impl Direction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Above => "above",
+            Direction::Below => "below",
+            Direction::LeftOf => "left",
+            Direction::RightOf => "right",
+            Direction::Near(_) => "near",
+        }
+    }
+}
+
+struct Filter {
+    direction: Direction,
+    anchor: Origin,
+}
+
+impl Serialize for Filter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut s = serializer.serialize_struct("Filter", 3)?;
+        s.serialize_field("kind", self.direction.as_str())?;
+        s.serialize_field("anchor", &self.anchor)?;
+        if let Direction::Near(distance) = self.direction {
+            s.serialize_field("distance", &distance)?;
+        }
+        s.end()
+    }
+}
+
+///
+/// 相对定位器，多个条件之间是且的关系
+///
+/// # Examples
+///
+/// ```no_run
+/// use selenium::option::FirefoxBuilder;
+/// use selenium::driver::Driver;
+/// use selenium::{locate_with, By};
+/// let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+/// let label = driver.find_element(By::Css("#email_label")).unwrap();
+/// let input = driver
+///     .find_element_relative(&locate_with(By::TagName("input")).right_of(&label))
+///     .unwrap();
+/// ```
+///
+pub struct RelativeBy<'a> {
+    pub(crate) root: By<'a>,
+    filters: Vec<Filter>,
+}
+
+/// 以 `by` 查找的元素作为候选，再通过位置关系过滤
+pub fn locate_with(by: By<'_>) -> RelativeBy<'_> {
+    RelativeBy::new(by)
+}
+
+impl<'a> RelativeBy<'a> {
+    pub fn new(by: By<'a>) -> Self {
+        RelativeBy {
+            root: by,
+            filters: Vec::new(),
+        }
+    }
+
+    fn filter(mut self, direction: Direction, element: &Element) -> Self {
+        self.filters.push(Filter {
+            direction,
+            anchor: element.origin(),
+        });
+        self
+    }
+    /// 位于元素上方
+    pub fn above(self, element: &Element) -> Self {
+        self.filter(Direction::Above, element)
+    }
+    /// 位于元素下方
+    pub fn below(self, element: &Element) -> Self {
+        self.filter(Direction::Below, element)
+    }
+    /// 位于元素左侧
+    pub fn left_of(self, element: &Element) -> Self {
+        self.filter(Direction::LeftOf, element)
+    }
+    /// 位于元素右侧
+    pub fn right_of(self, element: &Element) -> Self {
+        self.filter(Direction::RightOf, element)
+    }
+    /// 与元素的距离不超过 [DEFAULT_NEAR_DISTANCE] 像素
+    pub fn near(self, element: &Element) -> Self {
+        self.near_within(element, DEFAULT_NEAR_DISTANCE)
+    }
+    /// 与元素的距离不超过 `distance` 像素，距离按两个元素最近的边计算
+    pub fn near_within(self, element: &Element, distance: u32) -> Self {
+        self.filter(Direction::Near(distance), element)
+    }
+
+    /// 脚本参数，`candidates` 为 `root` 查找到的元素
+    pub(crate) fn args(&self, candidates: &[Element]) -> SResult<Vec<serde_json::Value>> {
+        Ok(vec![
+            serde_json::to_value(candidates.iter().map(Element::origin).collect::<Vec<_>>())?,
+            serde_json::to_value(&self.filters)?,
+        ])
+    }
+}
+
+pub(crate) const RELATIVE_SCRIPT: &str = include_str!("js/relative.js");
+
+#[cfg(test)]
+mod tests {
+    use crate::{By, Origin};
+
+    use super::{Direction, Filter, RelativeBy};
+
+    #[test]
+    fn serde() {
+        let mut r = RelativeBy::new(By::Id("name"));
+        r.filters.push(Filter {
+            direction: Direction::Above,
+            anchor: Origin::Element("element-6066".to_string(), "1".to_string()),
+        });
+        r.filters.push(Filter {
+            direction: Direction::Near(20),
+            anchor: Origin::Element("element-6066".to_string(), "2".to_string()),
+        });
+        let args = r.args(&[]).unwrap();
+        assert_eq!("[]", args[0].to_string());
+        assert_eq!(
+            r#"[{"anchor":{"ELEMENT":"1","element-6066":"1"},"kind":"above"},{"anchor":{"ELEMENT":"2","element-6066":"2"},"distance":20,"kind":"near"}]"#,
+            args[1].to_string()
+        );
+    }
+}
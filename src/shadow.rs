@@ -1,4 +1,9 @@
-use crate::{driver::Session, element::Element, http::Http, By, SResult};
+use crate::{
+    driver::{AsBy, Session},
+    element::Element,
+    http::Http,
+    By, SResult,
+};
 use std::{
     fmt::{Debug, Display},
     rc::Rc,
@@ -28,12 +33,16 @@ impl Display for Shadow {
 }
 
 impl Shadow {
-    pub fn find_element(&self, css: &str) -> SResult<Element> {
-        // TODO 2024-12-10 除了css之外的查找方式都报错了，似乎都有问题，只能暂时取消
+    ///
+    /// 在 shadow root 中查找
+    ///
+    /// 浏览器只支持 css 选择器，[By::Id]、[By::Class] 等会转换为 css，[By::Text] 等由脚本实现；
+    /// [By::XPath]、[By::LinkText] 会被浏览器拒绝
+    pub fn find_element<T: AsBy>(&self, by: T) -> SResult<Element> {
         let v = self.http.find_element_from_shadow_root(
             &self.session.session_id,
             &self.id,
-            By::Css(css),
+            by.as_by(),
         )?;
         Ok(Element {
             http: Rc::clone(&self.http),
@@ -43,19 +52,19 @@ impl Shadow {
         })
     }
 
-    pub fn find_elements(&self, css: &str) -> SResult<Vec<Element>> {
-        // TODO 2024-12-10 除了css之外的查找方式都报错了，似乎都有问题，只能暂时取消
+    /// 同 [Shadow::find_element]
+    pub fn find_elements<T: AsBy>(&self, by: T) -> SResult<Vec<Element>> {
         let v = self.http.find_elements_from_shadow_root(
             &self.session.session_id,
             &self.id,
-            By::Css(css),
+            by.as_by(),
         )?;
-        Ok(v.iter()
+        Ok(v.into_iter()
             .map(|f| Element {
                 http: Rc::clone(&self.http),
                 session: Rc::clone(&self.session),
-                identify: f.0.clone(),
-                id: f.1.clone(),
+                identify: f.0,
+                id: f.1,
             })
             .collect())
     }
@@ -0,0 +1,193 @@
+//!
+//! 常用的等待条件，配合 [Wait::until](super::Wait::until) 使用
+//!
+//! 条件返回 `Ok(None)` 表示继续等待，返回 `Ok(Some(_))` 表示条件已满足
+use crate::{
+    driver::{Driver, SwitchToFrame},
+    element::Element,
+    By, ErrorKind, SResult,
+};
+
+/// 装箱的条件，用于 [all_of] 和 [any_of]
+pub type Condition<'a, T> = Box<dyn Fn(&Driver) -> SResult<Option<T>> + 'a>;
+
+fn satisfied(v: bool) -> Option<()> {
+    if v {
+        Some(())
+    } else {
+        None
+    }
+}
+
+/// 元素不存在时返回 `Ok(None)`
+fn find<'a>(driver: &Driver, by: By<'a>) -> SResult<Option<Element>> {
+    match driver.find_element(by) {
+        Ok(ele) => Ok(Some(ele)),
+        Err(e) if e.kind() == ErrorKind::NoSuchElement => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// 标题等于 `title`
+pub fn title_is(title: &str) -> impl Fn(&Driver) -> SResult<Option<()>> + '_ {
+    move |driver| Ok(satisfied(driver.get_title()? == title))
+}
+
+/// 标题包含 `title`
+pub fn title_contains(title: &str) -> impl Fn(&Driver) -> SResult<Option<()>> + '_ {
+    move |driver| Ok(satisfied(driver.get_title()?.contains(title)))
+}
+
+/// 当前url满足正则，使用浏览器的 `RegExp` 匹配
+pub fn url_matches(pattern: &str) -> impl Fn(&Driver) -> SResult<Option<()>> + '_ {
+    move |driver| {
+        let url = driver.get_current_url()?;
+        let matched: bool = driver.execute_script(
+            "return new RegExp(arguments[0]).test(arguments[1]);",
+            &[pattern, url.as_str()],
+        )?;
+        Ok(satisfied(matched))
+    }
+}
+
+/// 出现alert，返回alert的文本
+pub fn alert_is_present() -> impl Fn(&Driver) -> SResult<Option<String>> {
+    |driver| match driver.get_alert_text() {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == ErrorKind::NoSuchAlert => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// frame 出现后切换进去
+pub fn frame_to_be_available_and_switch(
+    by: By<'_>,
+) -> impl Fn(&Driver) -> SResult<Option<()>> + '_ {
+    move |driver| {
+        let Some(frame) = find(driver, by)? else {
+            return Ok(None);
+        };
+        match driver.switch_to_frame(SwitchToFrame::Element(frame.id.clone())) {
+            Ok(_) => Ok(Some(())),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    ErrorKind::NoSuchFrame | ErrorKind::StaleElementReference
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// 元素已经从页面上移除
+pub fn staleness_of(element: &Element) -> impl Fn(&Driver) -> SResult<Option<()>> + '_ {
+    move |_| match element.is_enabled() {
+        Ok(_) => Ok(None),
+        Err(e)
+            if matches!(
+                e.kind(),
+                ErrorKind::StaleElementReference | ErrorKind::NoSuchElement
+            ) =>
+        {
+            Ok(Some(()))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// 元素存在
+pub fn presence_of(by: By<'_>) -> impl Fn(&Driver) -> SResult<Option<Element>> + '_ {
+    move |driver| find(driver, by)
+}
+
+/// 元素存在且可见
+pub fn visibility_of(by: By<'_>) -> impl Fn(&Driver) -> SResult<Option<Element>> + '_ {
+    move |driver| match find(driver, by)? {
+        Some(ele) if ele.is_displayed()? => Ok(Some(ele)),
+        _ => Ok(None),
+    }
+}
+
+/// 元素的文本包含 `text`
+pub fn text_to_be_present_in_element<'a>(
+    by: By<'a>,
+    text: &'a str,
+) -> impl Fn(&Driver) -> SResult<Option<()>> + 'a {
+    move |driver| {
+        let Some(ele) = find(driver, by)? else {
+            return Ok(None);
+        };
+        match ele.get_text() {
+            Ok(v) => Ok(satisfied(v.contains(text))),
+            Err(e) if e.kind() == ErrorKind::StaleElementReference => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// 元素可见并且可用
+pub fn element_to_be_clickable(by: By<'_>) -> impl Fn(&Driver) -> SResult<Option<Element>> + '_ {
+    move |driver| {
+        let Some(ele) = find(driver, by)? else {
+            return Ok(None);
+        };
+        match ele.is_displayed().and_then(|v| Ok(v && ele.is_enabled()?)) {
+            Ok(true) => Ok(Some(ele)),
+            Ok(false) => Ok(None),
+            Err(e) if e.kind() == ErrorKind::StaleElementReference => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// 窗口数量等于 `count`
+pub fn number_of_windows_to_be(count: usize) -> impl Fn(&Driver) -> SResult<Option<()>> {
+    move |driver| Ok(satisfied(driver.get_window_handles()?.len() == count))
+}
+
+/// 元素不存在或者不可见
+pub fn invisibility_of(by: By<'_>) -> impl Fn(&Driver) -> SResult<Option<()>> + '_ {
+    move |driver| {
+        let Some(ele) = find(driver, by)? else {
+            return Ok(Some(()));
+        };
+        match ele.is_displayed() {
+            Ok(v) => Ok(satisfied(!v)),
+            Err(e) if e.kind() == ErrorKind::StaleElementReference => Ok(Some(())),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// 所有条件都满足，按顺序返回每个条件的结果
+pub fn all_of<'a, T: 'a>(
+    conditions: Vec<Condition<'a, T>>,
+) -> impl Fn(&Driver) -> SResult<Option<Vec<T>>> + 'a {
+    move |driver| {
+        let mut res = Vec::with_capacity(conditions.len());
+        for condition in &conditions {
+            match condition(driver)? {
+                Some(v) => res.push(v),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(res))
+    }
+}
+
+/// 任意一个条件满足，返回第一个满足的条件的结果
+pub fn any_of<'a, T: 'a>(
+    conditions: Vec<Condition<'a, T>>,
+) -> impl Fn(&Driver) -> SResult<Option<T>> + 'a {
+    move |driver| {
+        for condition in &conditions {
+            if let Some(v) = condition(driver)? {
+                return Ok(Some(v));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,107 @@
+//!
+//! 显式等待
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use selenium::option::FirefoxBuilder;
+//! use selenium::driver::Driver;
+//! use selenium::wait::expected_conditions::{element_to_be_clickable, title_contains};
+//! use selenium::{By, ErrorKind};
+//! let driver = Driver::new(FirefoxBuilder::new().build()).unwrap();
+//! driver
+//!     .wait()
+//!     .timeout(Duration::from_secs(5))
+//!     .until(title_contains("github"))
+//!     .unwrap();
+//! let button = driver
+//!     .wait()
+//!     .polling(Duration::from_millis(200))
+//!     .ignore(ErrorKind::StaleElementReference)
+//!     .message("submit button not clickable")
+//!     .until(element_to_be_clickable(By::Id("submit")))
+//!     .unwrap();
+//! ```
+use std::{thread::sleep, time::Duration, time::Instant};
+
+use crate::{driver::Driver, ErrorKind, SError, SResult};
+
+pub mod expected_conditions;
+pub mod quiescence;
+
+pub(crate) const OBSERVE_SCRIPT: &str = include_str!("../js/observe.js");
+pub(crate) const NETWORK_SCRIPT: &str = include_str!("../js/network.js");
+
+/// 默认超时时间
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// 默认轮询间隔
+pub const DEFAULT_POLLING: Duration = Duration::from_millis(500);
+
+pub struct Wait<'a> {
+    driver: &'a Driver,
+    timeout: Duration,
+    polling: Duration,
+    ignored: Vec<ErrorKind>,
+    message: Option<String>,
+}
+
+impl<'a> Wait<'a> {
+    pub fn new(driver: &'a Driver) -> Self {
+        Wait {
+            driver,
+            timeout: DEFAULT_TIMEOUT,
+            polling: DEFAULT_POLLING,
+            ignored: Vec::new(),
+            message: None,
+        }
+    }
+    /// 超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    /// 两次检查之间的间隔
+    pub fn polling(mut self, polling: Duration) -> Self {
+        self.polling = polling;
+        self
+    }
+    /// 忽略该类型的错误，继续等待
+    pub fn ignore(mut self, kind: ErrorKind) -> Self {
+        self.ignored.push(kind);
+        self
+    }
+    /// 超时后 [SError::Timeout] 中的提示信息
+    pub fn message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    ///
+    /// 重复执行 `condition` 直到返回 `Some`
+    ///
+    /// 返回未被忽略的错误时立即结束等待
+    pub fn until<T, F>(&self, mut condition: F) -> SResult<T>
+    where
+        F: FnMut(&Driver) -> SResult<Option<T>>,
+    {
+        let start = Instant::now();
+        let mut last_error = None;
+        loop {
+            match condition(self.driver) {
+                Ok(Some(v)) => return Ok(v),
+                Ok(None) => {}
+                Err(e) if self.ignored.contains(&e.kind()) => last_error = Some(e),
+                Err(e) => return Err(e),
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= self.timeout {
+                let message = self.message.as_deref().unwrap_or("wait timeout");
+                return Err(SError::Timeout(match last_error {
+                    Some(e) => format!("{message}, last error: {e}"),
+                    None => message.to_string(),
+                }));
+            }
+            sleep(self.polling.min(self.timeout - elapsed));
+        }
+    }
+}
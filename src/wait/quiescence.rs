@@ -0,0 +1,80 @@
+//!
+//! 判断页面是否处于静止状态，配合 [Driver::wait_for_quiescence] 使用
+//!
+//! 探针是一段返回 `true` 或 `false` 的脚本，返回 `true` 表示页面已经静止
+use crate::driver::Driver;
+
+pub trait QuiescenceProbe {
+    /// 用于超时提示
+    fn name(&self) -> &str;
+    /// 页面静止时返回 `true` 的脚本
+    fn script(&self) -> &str;
+}
+
+/// Angular 的 testability，同时兼容 AngularJS 的 `$http.pendingRequests`
+pub struct Angular;
+
+impl QuiescenceProbe for Angular {
+    fn name(&self) -> &str {
+        "angular"
+    }
+
+    fn script(&self) -> &str {
+        r#"
+        if (window.getAllAngularTestabilities) {
+            return window.getAllAngularTestabilities().every(function (t) { return t.isStable(); });
+        }
+        if (window.angular && window.angular.element) {
+            var injector = window.angular.element(document.body).injector();
+            if (injector) {
+                return injector.get('$http').pendingRequests.length === 0;
+            }
+        }
+        return true;
+        "#
+    }
+}
+
+/// `jQuery.active` 为0，页面没有 jQuery 时直接通过
+pub struct JQuery;
+
+impl QuiescenceProbe for JQuery {
+    fn name(&self) -> &str {
+        "jQuery"
+    }
+
+    fn script(&self) -> &str {
+        "return !window.jQuery || window.jQuery.active === 0;"
+    }
+}
+
+///
+/// 自定义探针，比如页面自己暴露的钩子
+///
+/// ```
+/// use selenium::wait::quiescence::Custom;
+/// let probe = Custom::new("app", "return window.app && window.app.ready === true;");
+/// ```
+pub struct Custom {
+    name: String,
+    script: String,
+}
+
+impl Custom {
+    pub fn new(name: &str, script: &str) -> Self {
+        Custom {
+            name: name.to_string(),
+            script: script.to_string(),
+        }
+    }
+}
+
+impl QuiescenceProbe for Custom {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn script(&self) -> &str {
+        &self.script
+    }
+}
@@ -0,0 +1,34 @@
+//!
+//! xpath 相关的转义
+
+///
+/// 生成 xpath 字符串字面量
+///
+/// xpath 1.0 的字符串没有转义字符，同时包含单引号和双引号时使用 `concat()` 拼接
+pub fn quote(value: &str) -> String {
+    if !value.contains('\'') {
+        return format!("'{value}'");
+    }
+    if !value.contains('"') {
+        return format!("\"{value}\"");
+    }
+    let parts: Vec<String> = value.split('\'').map(|f| format!("'{f}'")).collect();
+    format!("concat({})", parts.join(", \"'\", "))
+}
+
+/// 用于 `[2]` 等位置谓语
+pub fn number<T: crate::css::Integer>(value: T) -> String {
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::quote;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!("'a'", quote("a"));
+        assert_eq!("\"it's\"", quote("it's"));
+        assert_eq!(r#"concat('say "it', "'", 's"')"#, quote(r#"say "it's""#));
+    }
+}
@@ -1,4 +1,7 @@
-use selenium::{By, Key};
+use selenium::{
+    actions::{Button, Origin, PointerSource},
+    By, Key,
+};
 
 mod common;
 #[test]
@@ -12,7 +15,10 @@ fn click() {
         .click(None)
         .perform()
         .unwrap();
-    assert_eq!("已点击", ele.get_property("innerHTML").unwrap().unwrap());
+    assert_eq!(
+        "已点击",
+        ele.get_property::<String>("innerHTML").unwrap().unwrap()
+    );
 }
 
 #[test]
@@ -29,7 +35,7 @@ fn key() {
         driver
             .find_element(By::Css("#demo"))
             .unwrap()
-            .get_property("innerHTML")
+            .get_property::<String>("innerHTML")
             .unwrap()
             .unwrap()
     );
@@ -38,7 +44,7 @@ fn key() {
         driver
             .find_element(By::Css("#demo2"))
             .unwrap()
-            .get_property("innerHTML")
+            .get_property::<String>("innerHTML")
             .unwrap()
             .unwrap()
     );
@@ -59,7 +65,7 @@ fn pause() {
         driver
             .find_element(By::Css("#demo"))
             .unwrap()
-            .get_property("innerHTML")
+            .get_property::<String>("innerHTML")
             .unwrap()
             .unwrap()
     );
@@ -68,7 +74,7 @@ fn pause() {
         driver
             .find_element(By::Css("#demo2"))
             .unwrap()
-            .get_property("innerHTML")
+            .get_property::<String>("innerHTML")
             .unwrap()
             .unwrap()
     );
@@ -90,7 +96,7 @@ fn key_special() {
         driver
             .find_element(By::Css("#demo"))
             .unwrap()
-            .get_property("innerHTML")
+            .get_property::<String>("innerHTML")
             .unwrap()
             .unwrap()
     );
@@ -99,7 +105,7 @@ fn key_special() {
         driver
             .find_element(By::Css("#demo2"))
             .unwrap()
-            .get_property("innerHTML")
+            .get_property::<String>("innerHTML")
             .unwrap()
             .unwrap()
     );
@@ -119,7 +125,7 @@ fn key_special() {
         driver
             .find_element(By::Css("#demo"))
             .unwrap()
-            .get_property("innerHTML")
+            .get_property::<String>("innerHTML")
             .unwrap()
             .unwrap()
     );
@@ -128,12 +134,36 @@ fn key_special() {
         driver
             .find_element(By::Css("#demo2"))
             .unwrap()
-            .get_property("innerHTML")
+            .get_property::<String>("innerHTML")
             .unwrap()
             .unwrap()
     );
 }
 
+#[test]
+fn modifier_click() {
+    let driver = common::new_driver();
+    let button = driver.find_element(By::Id("modifier_click")).unwrap();
+    // 按书写的顺序执行，点击时 ctrl 已经按下
+    driver
+        .actions()
+        .key_down_special(Key::Control)
+        .click(Some(&button))
+        .key_up_special(Key::Control)
+        .perform()
+        .unwrap();
+    assert_eq!(
+        Some("true".to_string()),
+        button.get_attribute("data-ctrl").unwrap()
+    );
+
+    driver.actions().click(Some(&button)).perform().unwrap();
+    assert_eq!(
+        Some("false".to_string()),
+        button.get_attribute("data-ctrl").unwrap()
+    );
+}
+
 #[test]
 fn scroll() {
     let driver = common::new_driver();
@@ -148,3 +178,154 @@ fn scroll() {
         .perform()
         .unwrap();
 }
+
+#[test]
+fn gesture() {
+    let driver = common::new_driver();
+    let area = driver.find_element(By::Id("touch_area")).unwrap();
+    driver
+        .actions()
+        .pinch(&area, 200, 40, 300)
+        .perform()
+        .unwrap();
+    assert_eq!(
+        Some("touch".to_string()),
+        area.get_attribute("data-type").unwrap()
+    );
+    assert_eq!(
+        Some("2".to_string()),
+        area.get_attribute("data-max").unwrap()
+    );
+    assert_eq!(
+        Some("2".to_string()),
+        area.get_attribute("data-up").unwrap()
+    );
+
+    driver
+        .actions()
+        .add_source(
+            PointerSource::pen("pen")
+                .move_to(Origin::from(&area), -50, 0, 0)
+                .down()
+                .move_to(Origin::Pointer, 100, 0, 200)
+                .up(),
+        )
+        .perform()
+        .unwrap();
+    assert_eq!(
+        Some("pen".to_string()),
+        area.get_attribute("data-type").unwrap()
+    );
+    assert_eq!(
+        Some("3".to_string()),
+        area.get_attribute("data-up").unwrap()
+    );
+
+    driver.actions().tap(&area).perform().unwrap();
+    driver.actions().long_press(&area, 200).perform().unwrap();
+    driver.actions().swipe(&area, 0, 50, 200).perform().unwrap();
+    driver
+        .actions()
+        .rotate(&area, 60, 90.0, 300)
+        .perform()
+        .unwrap();
+    assert_eq!(
+        Some("8".to_string()),
+        area.get_attribute("data-up").unwrap()
+    );
+}
+
+#[test]
+fn release_actions() {
+    let driver = common::new_driver();
+    let demo = driver.find_element(By::Css("#demo")).unwrap();
+    let action = driver.actions().key_down_special(Key::Control);
+    assert_eq!(1, action.pressed_keys().len());
+    action.perform().unwrap();
+    driver
+        .actions()
+        .key_down("e")
+        .key_up("e")
+        .perform()
+        .unwrap();
+    assert_eq!(
+        "keydown=e 69 ctrl",
+        demo.get_property::<String>("innerHTML").unwrap().unwrap()
+    );
+    driver.release_actions().unwrap();
+    driver
+        .actions()
+        .key_down("e")
+        .key_up("e")
+        .perform()
+        .unwrap();
+    assert_eq!(
+        "keydown=e 69",
+        demo.get_property::<String>("innerHTML").unwrap().unwrap()
+    );
+
+    let result: Result<(), selenium::SError> = driver.with_actions(|d| {
+        d.actions().key_down_special(Key::Control).perform()?;
+        d.find_element(By::Id("nothing")).map(|_| ())
+    });
+    assert!(result.is_err());
+    driver
+        .actions()
+        .key_down("e")
+        .key_up("e")
+        .perform()
+        .unwrap();
+    assert_eq!(
+        "keydown=e 69",
+        demo.get_property::<String>("innerHTML").unwrap().unwrap()
+    );
+}
+
+#[test]
+fn drag_and_drop() {
+    let driver = common::new_driver();
+    let source = driver.find_element(By::Id("drag_source")).unwrap();
+    let target = driver.find_element(By::Id("drop_target")).unwrap();
+    driver
+        .actions()
+        .drag_and_drop(&source, &target)
+        .perform()
+        .unwrap();
+    assert_eq!(
+        Some("drop_target".to_string()),
+        source.get_attribute("data-dropped").unwrap()
+    );
+
+    // drop_target 紧挨在下方，中心相距 50 像素
+    driver
+        .actions()
+        .interpolate(10)
+        .drag_and_drop_by(&source, 0, 50)
+        .perform()
+        .unwrap();
+    assert_eq!(
+        Some("drop_target".to_string()),
+        source.get_attribute("data-dropped").unwrap()
+    );
+    let moves: u32 = source
+        .get_attribute("data-moves")
+        .unwrap()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(moves >= 10);
+
+    driver
+        .actions()
+        .move_to(&source, 0, 0, 0)
+        .click_and_hold(None)
+        .move_by(-10, 0)
+        .move_to(Origin::Viewport, 1, 1, 100)
+        .release(Button::LEFT)
+        .perform()
+        .unwrap();
+    assert_eq!(
+        Some("".to_string()),
+        source.get_attribute("data-dropped").unwrap()
+    );
+}
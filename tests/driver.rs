@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-use selenium::{driver::Rect, By};
+use selenium::{
+    driver::{Rect, SwitchToFrame},
+    frame::FramePath,
+    By,
+};
 
 use crate::common::sleep;
 mod common;
@@ -106,7 +110,7 @@ fn dismiss_alert() {
 
     assert_eq!(
         "after alert",
-        ele.get_property("innerHTML").unwrap().unwrap()
+        ele.get_property::<String>("innerHTML").unwrap().unwrap()
     );
 }
 
@@ -120,7 +124,7 @@ fn confirm() {
 
     assert_eq!(
         "yes confirm",
-        ele.get_property("innerHTML").unwrap().unwrap()
+        ele.get_property::<String>("innerHTML").unwrap().unwrap()
     );
 
     ele.click().unwrap();
@@ -129,7 +133,7 @@ fn confirm() {
 
     assert_eq!(
         "no confirm",
-        ele.get_property("innerHTML").unwrap().unwrap()
+        ele.get_property::<String>("innerHTML").unwrap().unwrap()
     );
 }
 
@@ -151,7 +155,10 @@ fn prompt() {
     driver.send_alert_text("text").unwrap();
     driver.accept_alert().unwrap();
 
-    assert_eq!("text", ele.get_property("innerHTML").unwrap().unwrap());
+    assert_eq!(
+        "text",
+        ele.get_property::<String>("innerHTML").unwrap().unwrap()
+    );
 }
 
 #[test]
@@ -319,3 +326,383 @@ fn test_wait_until_element_not_exist() {
 
     assert_eq!(true, driver.find_element(By::Id("alert")).is_err());
 }
+
+#[test]
+fn find_element_relative() {
+    let driver = common::new_driver();
+    let center = driver.find_element(By::Id("relative_center")).unwrap();
+
+    let id = |e: selenium::element::Element| e.get_attribute("id").unwrap().unwrap();
+
+    let above = driver
+        .find_element_relative(&selenium::locate_with(By::Class("relative")).above(&center))
+        .unwrap();
+    assert_eq!("relative_above", id(above));
+
+    let below = driver
+        .find_element_relative(&selenium::locate_with(By::Class("relative")).below(&center))
+        .unwrap();
+    assert_eq!("relative_below", id(below));
+
+    // 按距离排序，最近的在前
+    let right = driver
+        .find_elements_relative(&selenium::locate_with(By::Class("relative")).right_of(&center))
+        .unwrap();
+    assert_eq!(
+        vec!["relative_right", "relative_far"],
+        right.into_iter().map(id).collect::<Vec<String>>()
+    );
+
+    let left = driver
+        .find_elements_relative(&selenium::locate_with(By::Class("relative")).left_of(&center))
+        .unwrap();
+    assert_eq!(1, left.len());
+
+    let near = driver
+        .find_elements_relative(&selenium::locate_with(By::Class("relative")).near(&center))
+        .unwrap();
+    assert_eq!(4, near.len());
+
+    let near = driver
+        .find_elements_relative(
+            &selenium::locate_with(By::Class("relative")).near_within(&center, 300),
+        )
+        .unwrap();
+    assert_eq!(5, near.len());
+
+    // 多个条件组合
+    let v = driver
+        .find_elements_relative(
+            &selenium::locate_with(By::Class("relative"))
+                .right_of(&center)
+                .near(&center),
+        )
+        .unwrap();
+    assert_eq!(1, v.len());
+
+    // 候选元素按普通的方式查找，支持脚本实现的查找方式
+    let right = driver
+        .find_element_relative(&selenium::locate_with(By::text("right")).right_of(&center))
+        .unwrap();
+    assert_eq!("relative_right", id(right));
+    assert!(driver
+        .find_element_relative(&selenium::locate_with(By::text("right")).left_of(&center))
+        .is_err());
+}
+
+#[test]
+fn wait_expected_conditions() {
+    use selenium::wait::expected_conditions::*;
+    use std::time::Duration;
+
+    let driver = common::new_driver();
+
+    driver.wait().until(title_is("测试")).unwrap();
+    driver.wait().until(title_contains("测")).unwrap();
+    driver.wait().until(url_matches(r"test\.html$")).unwrap();
+    driver.wait().until(number_of_windows_to_be(1)).unwrap();
+    driver
+        .wait()
+        .until(invisibility_of(By::Id("is_displayed_false")))
+        .unwrap();
+    driver
+        .wait()
+        .until(text_to_be_present_in_element(By::Id("p"), "文字"))
+        .unwrap();
+    let ele = driver
+        .wait()
+        .until(element_to_be_clickable(By::Id("click")))
+        .unwrap();
+    ele.click().unwrap();
+
+    // 超时
+    match driver
+        .wait()
+        .timeout(Duration::from_millis(1000))
+        .polling(Duration::from_millis(100))
+        .message("custom message")
+        .until(title_is("not exist"))
+    {
+        Err(selenium::SError::Timeout(msg)) => assert!(msg.contains("custom message")),
+        _ => panic!("should timeout"),
+    }
+
+    let all = driver
+        .wait()
+        .until(all_of(vec![
+            Box::new(title_is("测试")),
+            Box::new(number_of_windows_to_be(1)),
+        ]))
+        .unwrap();
+    assert_eq!(2, all.len());
+    driver
+        .wait()
+        .until(any_of(vec![
+            Box::new(title_is("not exist")),
+            Box::new(title_contains("测试")),
+        ]))
+        .unwrap();
+
+    // 5秒后元素被移除
+    let alert = driver.find_element(By::Id("alert")).unwrap();
+    driver.wait().until(staleness_of(&alert)).unwrap();
+
+    let p = driver.find_element(By::Id("prompt")).unwrap();
+    p.click().unwrap();
+    assert_eq!("ss", driver.wait().until(alert_is_present()).unwrap());
+    driver.dismiss_alert().unwrap();
+}
+
+#[test]
+fn wait_for_mutation() {
+    let driver = common::new_driver();
+    driver
+        .set_timeouts(selenium::TimeoutType::Script(1000))
+        .unwrap();
+    // 超时
+    match driver.wait_for_selector("#wait_until_element") {
+        Err(selenium::SError::Timeout(_)) => {}
+        v => panic!("should timeout {:?}", v),
+    }
+    // 记录超时的 observer 是否被断开，不会出现在 window 的属性中
+    let hidden: bool = driver
+        .execute_script(
+            "var o = window.__selenium_observer; var d = o.disconnect;
+            o.disconnect = function () { window.__disconnected = true; d.call(o); };
+            return Object.keys(window).indexOf('__selenium_observer') === -1;",
+            &[],
+        )
+        .unwrap();
+    assert!(hidden);
+
+    driver
+        .set_timeouts(selenium::TimeoutType::Script(8000))
+        .unwrap();
+    let footer = driver.find_element(By::Id("footer")).unwrap();
+    assert_eq!(
+        "footer changed",
+        driver
+            .wait_for_text_change(&footer)
+            .unwrap()
+            .get_text()
+            .unwrap()
+    );
+    let disconnected: bool = driver
+        .execute_script("return window.__disconnected === true;", &[])
+        .unwrap();
+    assert!(disconnected);
+    // 已经变化过了，再次等待会超时
+    driver
+        .set_timeouts(selenium::TimeoutType::Script(500))
+        .unwrap();
+    assert!(driver
+        .wait_for_attribute_change(&footer, "data-state")
+        .is_err());
+
+    driver
+        .set_timeouts(selenium::TimeoutType::Script(8000))
+        .unwrap();
+    let ele = driver.wait_for_selector("#wait_until_element").unwrap();
+    assert_eq!("wait_until_element", ele.get_text().unwrap());
+    driver.wait_for_selector_detached("#alert").unwrap();
+    assert!(driver.find_element(By::Id("alert")).is_err());
+}
+
+#[test]
+fn wait_for_quiescence() {
+    use selenium::wait::quiescence::{Custom, JQuery};
+    use std::time::Duration;
+
+    let driver = common::new_driver();
+    driver
+        .wait_for_document_ready(Duration::from_secs(5))
+        .unwrap();
+    driver
+        .wait_for_network_idle(Duration::from_millis(200), Duration::from_secs(5))
+        .unwrap();
+    driver
+        .wait_for_quiescence(&[&JQuery], Duration::from_secs(1))
+        .unwrap();
+
+    let never = Custom::new("never", "return false;");
+    match driver.wait_for_quiescence(&[&JQuery, &never], Duration::from_millis(500)) {
+        Err(selenium::SError::Timeout(m)) => assert!(m.contains("never")),
+        v => panic!("should timeout {:?}", v),
+    }
+}
+
+#[test]
+fn wait_for_network_idle() {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        time::{Duration, Instant},
+    };
+
+    // 1.5 秒后才响应的服务
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/slow", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0; 1024];
+        let _ = stream.read(&mut buf).unwrap();
+        std::thread::sleep(Duration::from_millis(1500));
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\nAccess-Control-Allow-Origin: *\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+            .unwrap();
+    });
+
+    let driver = common::new_driver();
+    // 注入统计脚本
+    driver
+        .wait_for_network_idle(Duration::from_millis(100), Duration::from_secs(5))
+        .unwrap();
+    let _: () = driver
+        .execute_script(
+            "fetch(arguments[0]).finally(function () { window.__fetched = true; });",
+            &[url.as_str()],
+        )
+        .unwrap();
+    let start = Instant::now();
+    // 请求进行中，不会空闲
+    match driver.wait_for_network_idle(Duration::from_millis(200), Duration::from_millis(500)) {
+        Err(selenium::SError::Timeout(_)) => {}
+        v => panic!("should timeout {:?}", v),
+    }
+    driver
+        .wait_for_network_idle(Duration::from_millis(200), Duration::from_secs(10))
+        .unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(1200));
+    let fetched: bool = driver
+        .execute_script("return window.__fetched === true;", &[])
+        .unwrap();
+    assert!(fetched);
+    server.join().unwrap();
+}
+
+#[test]
+fn auto_wait() {
+    use std::time::Duration;
+
+    let driver = common::new_driver();
+    let button = driver.find_element(By::Id("auto_wait_button")).unwrap();
+    // 被遮挡
+    assert!(button.click().is_err());
+
+    driver.set_auto_wait(Some(Duration::from_secs(5)));
+    assert_eq!(Some(Duration::from_secs(5)), driver.get_auto_wait());
+    button.click().unwrap();
+    assert_eq!("auto wait clicked", button.get_text().unwrap());
+
+    // 禁用的元素一直不可操作
+    driver.set_auto_wait(Some(Duration::from_millis(500)));
+    let disabled = driver.find_element(By::Id("disabled")).unwrap();
+    match disabled.click() {
+        Err(selenium::SError::Timeout(m)) => assert!(m.contains("disabled")),
+        v => panic!("should timeout {:?}", v),
+    }
+
+    // 失效的元素直接返回错误，不等到超时
+    driver.set_auto_wait(Some(Duration::from_secs(5)));
+    let _: () = driver
+        .execute_script("document.getElementById('disabled').remove();", &[])
+        .unwrap();
+    let start = std::time::Instant::now();
+    assert_eq!(
+        selenium::ErrorKind::StaleElementReference,
+        disabled.click().unwrap_err().kind()
+    );
+    assert!(start.elapsed() < Duration::from_secs(2));
+    driver.set_auto_wait(None);
+}
+
+#[test]
+fn locator() {
+    let driver = common::new_driver();
+    let p = driver.locator(By::Id("p"));
+    let element = p.element().unwrap();
+    // 模拟重新渲染
+    let _: Option<bool> = driver
+        .execute_script(
+            "var p = document.getElementById('p'); p.replaceWith(p.cloneNode(true));",
+            &[],
+        )
+        .unwrap();
+    assert!(element.get_text().is_err());
+    assert_eq!("测试文字", p.get_text().unwrap());
+
+    let relative = driver
+        .locator(By::TagName("body"))
+        .locator(By::Class("relative"));
+    assert_eq!(6, relative.count().unwrap());
+    assert_eq!("center", relative.first().get_text().unwrap());
+    assert_eq!("above", relative.nth(1).get_text().unwrap());
+    assert_eq!(1, relative.filter("far").count().unwrap());
+    assert!(relative.filter("nothing").click().is_err());
+    let texts: Vec<String> = relative
+        .all()
+        .unwrap()
+        .iter()
+        .map(|f| f.get_text().unwrap())
+        .collect();
+    assert_eq!(
+        vec!["center", "above", "below", "left", "right", "far"],
+        texts
+    );
+}
+
+#[test]
+fn locator_inside_frame() {
+    let driver = common::new_driver();
+    let outer = FramePath::new().then(By::Id("outer_frame"));
+    driver.switch_to_frame_path(&outer).unwrap();
+    // 没有指定 frame，在当前 frame 中查找，不切换
+    assert_eq!(
+        "outer frame",
+        driver.locator(By::Id("in_frame")).get_text().unwrap()
+    );
+    assert!(driver.find_element(By::Id("in_frame")).is_ok());
+
+    // 指定了 frame，从顶层开始进入，结束后回到调用前的 frame
+    let deep = driver
+        .locator(By::Id("deep"))
+        .frames(outer.then(By::Id("inner_frame")));
+    assert_eq!("inner frame", deep.get_text().unwrap());
+    assert!(driver.find_element(By::Id("in_frame")).is_ok());
+    assert!(deep.element().is_err());
+    driver.switch_to_frame(SwitchToFrame::Null).unwrap();
+    assert_eq!("inner frame", deep.get_text().unwrap());
+    assert!(driver.find_element(By::Id("p")).is_ok());
+}
+
+#[test]
+fn frame_path() {
+    let driver = common::new_driver();
+    let path = FramePath::new()
+        .then(By::Id("outer_frame"))
+        .then(By::Id("inner_frame"));
+    let deep = driver.locator(By::Id("deep")).frames(path.clone());
+    assert_eq!("inner frame", deep.get_text().unwrap());
+    // 操作结束后回到顶层文档
+    assert!(driver.find_element(By::Id("p")).is_ok());
+    let text = driver
+        .within_frames(&path, |d| d.find_element(By::Id("deep"))?.get_text())
+        .unwrap();
+    assert_eq!("inner frame", text);
+    assert!(driver.find_element(By::Id("deep")).is_err());
+
+    let (element, found) = driver.find_in_all_frames(By::Id("deep")).unwrap();
+    assert_eq!(2, found.len());
+    assert!(found.to_string().starts_with("top > css="));
+    // 找到后停留在元素所在的 frame
+    assert_eq!("inner frame", element.get_text().unwrap());
+    assert!(driver.find_element(By::Id("p")).is_err());
+    // 通过路径重新进入
+    driver.switch_to_frame(SwitchToFrame::Null).unwrap();
+    let text = driver
+        .within_frames(&found, |d| d.find_element(By::Id("deep"))?.get_text())
+        .unwrap();
+    assert_eq!("inner frame", text);
+    assert!(driver.find_in_all_frames(By::Id("nothing")).is_err());
+    assert!(driver.find_element(By::Id("p")).is_ok());
+}
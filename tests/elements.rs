@@ -1,4 +1,14 @@
-use selenium::By;
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::Duration,
+};
+
+use selenium::{
+    element::{DropFile, HitPoint, ScrollOptions},
+    By, TextQuery,
+};
+
+use crate::common::sleep;
 
 mod common;
 #[test]
@@ -16,8 +26,8 @@ fn get_property() {
     let driver = common::new_driver();
     let ele = driver.find_element(By::Css("#checkbox")).unwrap();
 
-    assert_eq!("1", ele.get_property("value").unwrap().unwrap());
-    assert_eq!(None, ele.get_property("ok").unwrap());
+    assert_eq!("1", ele.get_property::<String>("value").unwrap().unwrap());
+    assert_eq!(None, ele.get_property::<String>("ok").unwrap());
 
     let ele = driver.find_element(By::Id("href")).unwrap();
     assert_eq!(
@@ -25,7 +35,7 @@ fn get_property() {
             "file://{}/tests/2329",
             std::env::current_dir().unwrap().display()
         )),
-        ele.get_property("href").unwrap()
+        ele.get_property::<String>("href").unwrap()
     );
 
     let ele = driver.find_element(By::Id("src")).unwrap();
@@ -34,7 +44,7 @@ fn get_property() {
             "file://{}/tests/common/1.png",
             std::env::current_dir().unwrap().display()
         )),
-        ele.get_property("src").unwrap()
+        ele.get_property::<String>("src").unwrap()
     );
 }
 
@@ -111,11 +121,14 @@ fn clear() {
     let driver = common::new_driver();
     let ele = driver.find_element(By::Css("#clear")).unwrap();
 
-    assert_eq!("测试clear", ele.get_property("value").unwrap().unwrap());
+    assert_eq!(
+        "测试clear",
+        ele.get_property::<String>("value").unwrap().unwrap()
+    );
 
     ele.clear().unwrap();
 
-    assert_eq!("", ele.get_property("value").unwrap().unwrap());
+    assert_eq!("", ele.get_property::<String>("value").unwrap().unwrap());
 }
 
 #[test]
@@ -125,7 +138,10 @@ fn send_keys() {
     ele.clear().unwrap();
     ele.send_keys("demo测试").unwrap();
 
-    assert_eq!("demo测试", ele.get_property("value").unwrap().unwrap());
+    assert_eq!(
+        "demo测试",
+        ele.get_property::<String>("value").unwrap().unwrap()
+    );
 }
 
 #[test]
@@ -155,3 +171,522 @@ fn is_displayed() {
             .unwrap()
     );
 }
+
+#[test]
+fn find_element_escaped() {
+    let driver = common::new_driver();
+    for by in [
+        By::Id("form:user.name"),
+        By::Class("1st"),
+        By::Name("user"),
+        By::Attribute("data-testid", "user \"input\""),
+    ] {
+        let ele = driver.find_element(by).unwrap();
+        assert_eq!(
+            "escape",
+            ele.get_property::<String>("value").unwrap().unwrap()
+        );
+    }
+
+    let by: selenium::ByBuf = serde_json::from_str(r#"{"name":"user"}"#).unwrap();
+    assert_eq!("name=user", by.to_string());
+    assert!(driver.find_element(by.as_by()).is_ok());
+}
+
+#[test]
+fn find_element_by_text() {
+    let driver = common::new_driver();
+    // 文字分散在子元素中，并且有多余的空白
+    let ele = driver.find_element(By::text("Save All Items")).unwrap();
+    assert_eq!("text_split", ele.get_attribute("id").unwrap().unwrap());
+    // 隐藏的元素不会返回
+    assert_eq!(
+        1,
+        driver
+            .find_elements(By::text_contains("All Items"))
+            .unwrap()
+            .len()
+    );
+    assert!(driver.find_element(By::text("save all items")).is_err());
+    assert!(driver
+        .find_element(By::text(TextQuery::ignore_case("save all items")))
+        .is_ok());
+    assert!(driver
+        .find_element(By::text_regex(TextQuery::ignore_case("^Save\\s+all")))
+        .is_ok());
+
+    let parent = driver.find_element(By::Id("text_parent")).unwrap();
+    let ele = parent.find_element(By::text_contains("All")).unwrap();
+    assert_eq!("b", ele.get_tag_name().unwrap());
+
+    let shadow = driver
+        .find_element(By::Id("shadow_host"))
+        .unwrap()
+        .get_shadow_root()
+        .unwrap();
+    let ele = shadow.find_element(By::text("inside shadow")).unwrap();
+    assert_eq!("shadow_text", ele.get_attribute("id").unwrap().unwrap());
+    assert_eq!(
+        1,
+        shadow.find_elements(By::Id("shadow_text")).unwrap().len()
+    );
+    // 字符串作为 css 选择器
+    assert!(shadow.find_element("#shadow_text").is_ok());
+    assert_eq!(1, shadow.find_elements("p").unwrap().len());
+}
+
+#[test]
+fn find_element_any_of() {
+    let driver = common::new_driver();
+    let list = [
+        By::Id("not_exist"),
+        By::Css("[[invalid"),
+        By::Name("user"),
+        By::Id("p"),
+    ];
+    let ele = driver.find_element(By::any_of(&list)).unwrap();
+    assert_eq!(
+        "escape",
+        ele.get_property::<String>("value").unwrap().unwrap()
+    );
+    assert_eq!(1, driver.find_elements(By::any_of(&list)).unwrap().len());
+
+    let body = driver.find_element(By::TagName("body")).unwrap();
+    assert!(body.find_element(By::any_of(&list)).is_ok());
+    assert!(driver
+        .find_element(By::any_of(&[By::Id("not_exist"), By::Id("not_exist2")]))
+        .is_err());
+    assert!(driver
+        .wait_until_element(By::any_of(&[By::Id("not_exist"), By::Id("p")]), 1000)
+        .is_ok());
+}
+
+#[test]
+fn unique_selector() {
+    use selenium::element::Specificity;
+
+    let driver = common::new_driver();
+    let p = driver.find_element(By::Id("p")).unwrap();
+    let selector = p.unique_selector().unwrap();
+    assert_eq!("#p", selector.selector);
+    assert_eq!(Specificity::Id, selector.specificity);
+    assert_eq!("//p[@id='p']", p.xpath().unwrap().selector);
+
+    let b = driver
+        .find_element(By::Id("text_split"))
+        .unwrap()
+        .find_element(By::TagName("b"))
+        .unwrap();
+    for selector in [b.unique_selector().unwrap(), b.xpath().unwrap()] {
+        assert_eq!(Specificity::Tag, selector.specificity);
+        let found = driver.find_element(&selector).unwrap();
+        assert_eq!("All", found.get_text().unwrap());
+    }
+
+    let input = driver.find_element(By::Name("user")).unwrap();
+    let found = driver
+        .find_element(input.unique_selector().unwrap())
+        .unwrap();
+    assert_eq!(
+        "escape",
+        found.get_property::<String>("value").unwrap().unwrap()
+    );
+}
+
+#[test]
+fn inspect() {
+    let driver = common::new_driver();
+    let elements = vec![
+        driver.find_element(By::Id("rect")).unwrap(),
+        driver.find_element(By::Id("checkbox2")).unwrap(),
+        driver.find_element(By::Id("disabled")).unwrap(),
+    ];
+    let rows = driver
+        .inspect(
+            &elements,
+            &[
+                "text",
+                "tag",
+                "attr:id",
+                "attr:missing",
+                "prop:checked",
+                "displayed",
+                "enabled",
+            ],
+        )
+        .unwrap();
+    assert_eq!(3, rows.len());
+    assert_eq!(Some("测试rect"), rows[0].text.as_deref());
+    assert_eq!(Some("input"), rows[1].tag_name.as_deref());
+    assert_eq!(Some("checkbox2"), rows[1].attr("id"));
+    assert_eq!(None, rows[1].attr("missing"));
+    assert_eq!(Some(true), rows[1].property::<bool>("checked"));
+    assert_eq!(Some(false), rows[2].enabled);
+    assert!(rows[0].rect.is_none());
+    assert!(driver.inspect(&elements, &["size"]).is_err());
+
+    // 对象和集合类型的属性转为 json 结构
+    let input = driver.find_element(By::Id("form:user.name")).unwrap();
+    let row = driver
+        .inspect(
+            &[input],
+            &["prop:dataset", "prop:validity", "prop:classList"],
+        )
+        .unwrap()
+        .remove(0);
+    assert_eq!(
+        Some("user \"input\"".to_string()),
+        row.property::<serde_json::Value>("dataset")
+            .and_then(|f| f["testid"].as_str().map(str::to_string))
+    );
+    assert_eq!(
+        Some(true),
+        row.property::<serde_json::Value>("validity")
+            .and_then(|f| f["valid"].as_bool())
+    );
+    assert_eq!(
+        Some(vec!["1st".to_string()]),
+        row.property::<Vec<String>>("classList")
+    );
+
+    let snapshot = elements[0].snapshot().unwrap();
+    assert_eq!("div", snapshot.tag_name);
+    assert_eq!("测试rect", snapshot.text);
+    let rect = elements[0].get_rect().unwrap();
+    assert_eq!(rect.x, snapshot.rect.x);
+    assert_eq!(rect.width, snapshot.rect.width);
+    assert!(snapshot.rect.width.unwrap() > 0.0);
+    assert!(snapshot.displayed);
+    assert_eq!(Some(&"rect".to_string()), snapshot.attributes.get("id"));
+    assert_eq!(3, driver.snapshots(&elements).unwrap().len());
+}
+
+#[test]
+fn dom_helpers() {
+    let driver = common::new_driver();
+    let p = driver.find_element(By::Id("p")).unwrap();
+    assert_eq!("测试文字", p.inner_html().unwrap());
+    assert_eq!(r#"<p id="p">测试文字</p>"#, p.outer_html().unwrap());
+    p.set_inner_html("<b>bold</b>").unwrap();
+    assert_eq!(
+        "bold",
+        p.find_element(By::TagName("b"))
+            .unwrap()
+            .get_text()
+            .unwrap()
+    );
+
+    let input = driver.find_element(By::Id("clear")).unwrap();
+    let _: () = driver
+        .execute_script(
+            "var e = document.getElementById('clear'); e.addEventListener('input', () => e.dataset.input = e.value); e.addEventListener('change', () => e.dataset.change = e.value);",
+            &[],
+        )
+        .unwrap();
+    input.set_value_js("by script").unwrap();
+    assert_eq!(
+        Some("by script".to_string()),
+        input.get_attribute("data-input").unwrap()
+    );
+    assert_eq!(
+        Some("by script".to_string()),
+        input.get_attribute("data-change").unwrap()
+    );
+
+    input.focus().unwrap();
+    assert!(driver
+        .get_active_element()
+        .unwrap()
+        .outer_html()
+        .unwrap()
+        .contains("id=\"clear\""));
+    input.blur().unwrap();
+    assert!(!driver
+        .get_active_element()
+        .unwrap()
+        .outer_html()
+        .unwrap()
+        .contains("id=\"clear\""));
+
+    let _: () = driver
+        .execute_script(
+            "document.getElementById('p').addEventListener('custom', e => { e.target.dataset.detail = e.detail.id; e.preventDefault(); });",
+            &[],
+        )
+        .unwrap();
+    assert!(!p
+        .dispatch_event("custom", serde_json::json!({"detail": {"id": 7}}))
+        .unwrap());
+    assert_eq!(
+        Some("7".to_string()),
+        p.get_attribute("data-detail").unwrap()
+    );
+    assert!(input.dispatch_event("focus", ()).unwrap());
+
+    let footer = driver.find_element(By::Id("footer")).unwrap();
+    footer.scroll_into_view(ScrollOptions::center()).unwrap();
+    let rect = footer.get_bounding_client_rect().unwrap();
+    let height: f64 = driver
+        .execute_script("return window.innerHeight", &[])
+        .unwrap();
+    assert!(rect.top >= 0.0 && rect.bottom <= height);
+
+    p.highlight(Duration::from_millis(500)).unwrap();
+    assert!(p.get_attribute("style").unwrap().unwrap().contains("red"));
+    sleep(1);
+    assert!(!p
+        .get_attribute("style")
+        .unwrap()
+        .unwrap_or_default()
+        .contains("red"));
+}
+
+#[test]
+fn typed_attributes() {
+    let driver = common::new_driver();
+    let checkbox = driver.find_element(By::Id("checkbox2")).unwrap();
+    assert_eq!(
+        Some(true),
+        checkbox.get_property::<bool>("checked").unwrap()
+    );
+    checkbox.click().unwrap();
+    assert_eq!(Some(false), checkbox.get_dom_property("checked").unwrap());
+    // html 中的属性不会随点击变化，布尔属性按协议返回 "true"
+    assert_eq!(
+        Some("true".to_string()),
+        checkbox.get_dom_attribute("checked").unwrap()
+    );
+    assert_eq!(
+        Some(&String::new()),
+        checkbox.get_attributes().unwrap().get("checked")
+    );
+    assert!(checkbox.has_attribute("checked").unwrap());
+    assert!(!checkbox.has_attribute("missing").unwrap());
+    let parent = driver.find_element(By::Id("text_parent")).unwrap();
+    assert_eq!(
+        Some(1),
+        parent.get_property::<u32>("childElementCount").unwrap()
+    );
+
+    let href = driver.find_element(By::Id("href")).unwrap();
+    assert_eq!(
+        Some("../2329".to_string()),
+        href.get_dom_attribute("href").unwrap()
+    );
+    assert!(href
+        .get_dom_property::<String>("href")
+        .unwrap()
+        .unwrap()
+        .ends_with("/tests/2329"));
+
+    let input = driver.find_element(By::Name("user")).unwrap();
+    let attributes = input.get_attributes().unwrap();
+    assert_eq!(
+        vec!["class", "data-testid", "id", "name", "value"],
+        attributes.keys().collect::<Vec<_>>()
+    );
+    assert_eq!("form:user.name", attributes["id"]);
+    assert_eq!(vec!["1st"], input.get_class_list().unwrap());
+    let data = input.get_data_attributes().unwrap();
+    assert_eq!(Some(&"user \"input\"".to_string()), data.get("testid"));
+    let dataset: BTreeMap<String, String> = input.get_property("dataset").unwrap().unwrap();
+    assert_eq!(data, dataset);
+}
+
+#[test]
+fn tree_navigation() {
+    let driver = common::new_driver();
+    let b = driver.find_element(By::Css("#text_split b")).unwrap();
+    let split = b.parent().unwrap().unwrap();
+    assert_eq!(
+        Some("text_split".to_string()),
+        split.get_attribute("id").unwrap()
+    );
+    assert_eq!(0, b.index_in_parent().unwrap());
+    assert_eq!(1, split.children().unwrap().len());
+    let parent = b.closest("div").unwrap().unwrap();
+    assert_eq!(
+        Some("text_parent".to_string()),
+        parent.get_attribute("id").unwrap()
+    );
+    assert!(b.closest("table").unwrap().is_none());
+    let ancestors = b.ancestors().unwrap();
+    assert_eq!(
+        vec!["span", "div", "body", "html"],
+        ancestors
+            .iter()
+            .map(|f| f.get_tag_name().unwrap())
+            .collect::<Vec<_>>()
+    );
+    assert!(ancestors[3].parent().unwrap().is_none());
+
+    let next = parent.next_sibling().unwrap().unwrap();
+    assert_eq!("span", next.get_tag_name().unwrap());
+    let previous = next.previous_sibling().unwrap().unwrap();
+    assert_eq!(
+        Some("text_parent".to_string()),
+        previous.get_attribute("id").unwrap()
+    );
+    assert_eq!(
+        parent.index_in_parent().unwrap() + 1,
+        next.index_in_parent().unwrap()
+    );
+    assert!(b.next_sibling().unwrap().is_none());
+}
+
+#[test]
+fn identity() {
+    let driver = common::new_driver();
+    let input = driver.find_element(By::Id("clear")).unwrap();
+    assert_eq!(input, driver.find_element(By::Css("#clear")).unwrap());
+    assert_ne!(input, driver.find_element(By::Id("p")).unwrap());
+    let copy = input.clone();
+    assert_eq!(input, copy);
+    copy.click().unwrap();
+    assert_eq!(input, driver.get_active_element().unwrap());
+
+    let mut set = HashSet::new();
+    set.extend(driver.find_elements(By::Css("button")).unwrap());
+    set.extend(driver.find_elements(By::Css("#disabled, #click")).unwrap());
+    assert_eq!(
+        driver.find_elements(By::Css("button")).unwrap().len(),
+        set.len()
+    );
+
+    let json = serde_json::to_value(&input).unwrap();
+    let json = json.as_object().unwrap();
+    assert_eq!(1, json.len());
+    assert!(json.contains_key("element-6066-11e4-a52e-4f735466cecf"));
+    let id: String = driver
+        .execute_script_with("return arguments[0].id + arguments[1];", (&input, 1))
+        .unwrap();
+    assert_eq!("clear1", id);
+    let same: bool = driver
+        .execute_script_with("return arguments[0] === document.activeElement;", (&input,))
+        .unwrap();
+    assert!(same);
+    let count: usize = driver
+        .execute_script_with("return arguments.length;", (vec![1, 2, 3],))
+        .unwrap();
+    assert_eq!(1, count);
+    let count: usize = driver
+        .execute_script_with("return arguments.length;", vec![1, 2, 3])
+        .unwrap();
+    assert_eq!(3, count);
+}
+
+#[test]
+fn viewport_visibility() {
+    let driver = common::new_driver();
+    let _: () = driver
+        .execute_script(
+            "var s = document.createElement('div'); s.style.height = '3000px'; document.body.append(s);
+            var h = document.createElement('div'); h.id = 'sticky';
+            h.style.cssText = 'position: fixed; top: 0; left: 0; width: 100%; height: 60px; z-index: 10; background: #fff;';
+            document.body.append(h);",
+            &[],
+        )
+        .unwrap();
+    let rect = driver.find_element(By::Id("rect")).unwrap();
+    let obscured = rect.is_obscured().unwrap().unwrap();
+    assert_eq!(
+        Some("sticky".to_string()),
+        obscured.by.get_attribute("id").unwrap()
+    );
+    assert_eq!(5, obscured.points.len());
+    assert!(obscured.points.contains(&HitPoint::Center));
+
+    let visibility = rect.is_in_viewport(1.0).unwrap();
+    assert!(visibility.in_viewport);
+    assert_eq!(1.0, visibility.ratio);
+    assert_eq!(29.0, visibility.rect.left);
+
+    // rect 在文档中的位置是 10 到 48
+    let _: () = driver
+        .execute_script("window.scrollTo(0, 29)", &[])
+        .unwrap();
+    assert!((rect.visible_ratio().unwrap() - 0.5).abs() < 0.01);
+    assert!(!rect.is_in_viewport(0.6).unwrap().in_viewport);
+    assert!(rect.is_in_viewport(0.0).unwrap().in_viewport);
+
+    let _: () = driver
+        .execute_script("window.scrollTo(0, 1000)", &[])
+        .unwrap();
+    assert_eq!(0.0, rect.visible_ratio().unwrap());
+    assert!(rect.is_obscured().unwrap().is_none());
+    let _: () = driver
+        .execute_script(
+            "document.getElementById('sticky').remove(); window.scrollTo(0, 0)",
+            &[],
+        )
+        .unwrap();
+    assert!(rect.is_obscured().unwrap().is_none());
+}
+
+#[test]
+fn is_displayed_cache() {
+    let driver = common::new_driver();
+    let hidden = driver.find_element(By::Css("#text_parent + span")).unwrap();
+    assert!(!hidden.is_displayed().unwrap());
+    // 查找文字时把 isDisplayed 缓存到页面中，不可枚举
+    driver.find_element(By::text("Save All Items")).unwrap();
+    let cached: bool = driver
+        .execute_script(
+            "return typeof window.__selenium_is_displayed === 'function' && Object.keys(window).indexOf('__selenium_is_displayed') < 0",
+            &[],
+        )
+        .unwrap();
+    assert!(cached);
+    // 刷新后缓存失效，需要重新注入
+    driver.refresh().unwrap();
+    driver.find_element(By::text("Save All Items")).unwrap();
+    let p = driver.find_element(By::Id("p")).unwrap();
+    assert!(p.is_displayed().unwrap());
+    assert_eq!(
+        Some(true),
+        driver.inspect(&[p], &["displayed"]).unwrap()[0].displayed
+    );
+}
+
+#[test]
+fn html5_drag_and_drop() {
+    let driver = common::new_driver();
+    let card = driver.find_element(By::Id("html5_card")).unwrap();
+    let column = driver.find_element(By::Id("html5_column")).unwrap();
+    assert!(card.html5_drag_to(&column).unwrap());
+    assert_eq!(
+        Some("card-1".to_string()),
+        column.get_attribute("data-text").unwrap()
+    );
+    assert_eq!(
+        Some("dragstart,dragend".to_string()),
+        card.get_attribute("data-events").unwrap()
+    );
+    assert_eq!(
+        Some("move".to_string()),
+        card.get_attribute("data-effect").unwrap()
+    );
+
+    // 没有取消 dragover，不接受放置
+    let locked = driver.find_element(By::Id("html5_locked")).unwrap();
+    assert!(!card.html5_drag_to(&locked).unwrap());
+    assert_eq!(
+        Some("none".to_string()),
+        card.get_attribute("data-effect").unwrap()
+    );
+
+    assert!(column
+        .drop_files(&[
+            DropFile::new("a.txt", "hello").mime("text/plain"),
+            DropFile::new("b.bin", vec![0u8, 255]),
+        ])
+        .unwrap());
+    assert_eq!(
+        Some(String::new()),
+        column.get_attribute("data-text").unwrap()
+    );
+    assert_eq!(
+        Some("a.txt:5:text/plain,b.bin:2:".to_string()),
+        column.get_attribute("data-files").unwrap()
+    );
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "derive")]
+use selenium::{
+    driver::Driver,
+    element::Element,
+    page::{Component, Find, FindAll},
+    PageObject,
+};
+
+mod common;
+
+#[derive(PageObject)]
+struct TestPage<'a> {
+    #[root]
+    driver: &'a Driver,
+    #[find(css = "#p")]
+    p: Find,
+    #[find(id = "clear")]
+    clear: Find,
+    #[find_all(tag_name = "label")]
+    labels: FindAll,
+    #[find(id = "wait_until_element")]
+    #[wait(timeout_ms = 6000)]
+    later: Find,
+    #[find(id = "label")]
+    label: Component<Label>,
+    #[find_all(tag_name = "label")]
+    all_labels: Component<Label>,
+}
+
+#[derive(PageObject)]
+struct Label {
+    #[root]
+    root: Element,
+    #[find(xpath = "./input")]
+    input: Find,
+}
+
+#[test]
+fn page_object() {
+    let driver = common::new_driver();
+    let page = TestPage::new(&driver);
+
+    assert_eq!("测试文字", page.p().unwrap().get_text().unwrap());
+    page.clear().unwrap().clear().unwrap();
+    assert_eq!(2, page.labels().unwrap().len());
+
+    assert_eq!(
+        "1",
+        page.label()
+            .unwrap()
+            .input()
+            .unwrap()
+            .get_attribute("value")
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        vec!["1", "2"],
+        page.all_labels()
+            .unwrap()
+            .iter()
+            .map(|f| f.input().unwrap().get_attribute("value").unwrap().unwrap())
+            .collect::<Vec<String>>()
+    );
+
+    assert_eq!(
+        "wait_until_element",
+        page.later().unwrap().get_text().unwrap()
+    );
+}
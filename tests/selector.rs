@@ -0,0 +1,53 @@
+#![cfg(feature = "macros")]
+use selenium::{css, css::Css, xpath, By, ByBuf};
+
+mod common;
+
+#[test]
+fn selector_macros() {
+    assert_eq!(
+        By::Css("form > input[type=submit]"),
+        css!("form > input[type=submit]")
+    );
+    assert_eq!(By::XPath("//div[@id='a']"), xpath!("//div[@id='a']"));
+
+    let id = "form:user.name";
+    let value = "a\"b";
+    assert_eq!(
+        ByBuf::Css(r#"#form\:user\.name[name="a\"b"]"#.to_string()),
+        css!("#{}[name={}]", id, value)
+    );
+    assert_eq!(
+        ByBuf::XPath(r#"//a[text()=concat('it', "'", 's "ok"')]"#.to_string()),
+        xpath!("//a[text()={}]", r#"it's "ok""#)
+    );
+    assert_eq!(
+        ByBuf::Css("li:nth-child(2):not(.\\31 st)".to_string()),
+        css!("li:nth-child({}):not(.{})", 2, "1st")
+    );
+    assert_eq!(
+        ByBuf::Css("p:lang(en\\)\\ a)".to_string()),
+        css!("p:lang({})", "en) a")
+    );
+    assert_eq!(
+        ByBuf::XPath("(//li)[2]/a[@id='x']".to_string()),
+        xpath!("(//li)[{}]/a[@id={}]", 2, "x")
+    );
+}
+
+#[test]
+fn find_element_with_selector() {
+    let driver = common::new_driver();
+    let id = "form:user.name";
+    let ele = driver.find_element(css!("#{}", id)).unwrap();
+    assert_eq!(
+        "escape",
+        ele.get_property::<String>("value").unwrap().unwrap()
+    );
+
+    let css = Css::tag("input").class("1st").attr("name", "user");
+    assert!(driver.find_element(&css).is_ok());
+    assert!(driver
+        .find_element(xpath!("//input[@name={}]", "user"))
+        .is_ok());
+}